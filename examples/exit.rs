@@ -0,0 +1,20 @@
+//! Exits with a code depending on the severity of the error given as argument
+//!
+//! `cargo run --example exit -- fatal` exits with `EX_SOFTWARE`, `error` with `EX_TEMPFAIL`
+//! and anything else successfully.
+use fatal_error::{exit::ExitReport, Context, FatalError, FatalResultExt};
+use std::io;
+
+fn run(severity: &str) -> Result<(), FatalError<io::Error>> {
+    let error = io::Error::other("connection refused");
+    match severity {
+        "error" => Err(FatalError::Error(error)),
+        "fatal" => Err(FatalError::Fatal(error)),
+        _ => Ok(()),
+    }
+}
+
+fn main() -> ExitReport<Context<&'static str, io::Error>> {
+    let severity = std::env::args().nth(1).unwrap_or_default();
+    run(&severity).context("while loading config").into()
+}
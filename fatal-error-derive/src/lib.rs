@@ -0,0 +1,172 @@
+//! Derive macro for the `Fatality` trait of the `fatal-error` crate
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Error, Fields,
+    Type,
+};
+
+/// Derives `Fatality` for an enum or a struct
+///
+/// Every variant is classified with one of the following attributes:
+/// - `#[fatal]`: the variant is a fatal error
+/// - `#[recoverable]`: the variant is a non fatal error
+/// - `#[fatality(transparent)]`: the variant holds a single field whose `Fatality` is used
+///
+/// The same attributes can be put on the enum to set the classification of the variants
+/// without attribute. On a struct the attribute classifies the struct itself.
+///
+/// Also generates `From<Self> for FatalError<Self>` and an `into_fatal_error` method.
+#[proc_macro_derive(Fatality, attributes(fatal, recoverable, fatality))]
+pub fn derive_fatality(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+/// classification of a type or a variant
+#[derive(Clone, Copy)]
+enum Kind {
+    Fatal,
+    Recoverable,
+    Transparent,
+}
+
+fn kind(attrs: &[Attribute]) -> syn::Result<Option<Kind>> {
+    let mut kind = None;
+    for attr in attrs {
+        let current = if attr.path().is_ident("fatal") {
+            attr.meta.require_path_only()?;
+            Kind::Fatal
+        } else if attr.path().is_ident("recoverable") {
+            attr.meta.require_path_only()?;
+            Kind::Recoverable
+        } else if attr.path().is_ident("fatality") {
+            let mut transparent = false;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("transparent") {
+                    transparent = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `transparent`"))
+                }
+            })?;
+            if !transparent {
+                return Err(Error::new_spanned(
+                    attr,
+                    "expected `#[fatality(transparent)]`",
+                ));
+            }
+            Kind::Transparent
+        } else {
+            continue;
+        };
+        if kind.replace(current).is_some() {
+            return Err(Error::new_spanned(attr, "conflicting fatality attributes"));
+        }
+    }
+    Ok(kind)
+}
+
+/// return the pattern matching the fields and the expression classifying them
+fn classify(
+    kind: Kind, fields: &Fields, span: &dyn Spanned, bounds: &mut Vec<Type>,
+) -> syn::Result<(TokenStream2, TokenStream2)> {
+    match kind {
+        Kind::Fatal => Ok((quote!({ .. }), quote!(true))),
+        Kind::Recoverable => Ok((quote!({ .. }), quote!(false))),
+        Kind::Transparent => {
+            let mut iter = fields.iter();
+            let (Some(field), None) = (iter.next(), iter.next()) else {
+                return Err(Error::new(
+                    span.span(),
+                    "`#[fatality(transparent)]` requires exactly one field",
+                ));
+            };
+            bounds.push(field.ty.clone());
+            let pattern = match &field.ident {
+                Some(x) => quote!({ #x: __field }),
+                None => quote!((__field)),
+            };
+            Ok((
+                pattern,
+                quote_spanned!(field.ty.span()=> ::fatal_error::Fatality::is_fatal(__field)),
+            ))
+        }
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let default = kind(&input.attrs)?;
+    let mut bounds = Vec::new();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let kind = default.ok_or_else(|| {
+                Error::new_spanned(
+                    &input.ident,
+                    "missing `#[fatal]`, `#[recoverable]` or `#[fatality(transparent)]`",
+                )
+            })?;
+            let (pattern, value) =
+                classify(kind, &data.fields, &input.ident, &mut bounds)?;
+            quote!(match self { Self #pattern => #value })
+        }
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let kind = kind(&variant.attrs)?.or(default).ok_or_else(|| {
+                    Error::new_spanned(
+                        &variant.ident,
+                        "missing `#[fatal]`, `#[recoverable]` or `#[fatality(transparent)]`",
+                    )
+                })?;
+                let ident = &variant.ident;
+                let (pattern, value) =
+                    classify(kind, &variant.fields, ident, &mut bounds)?;
+                arms.push(quote!(Self::#ident #pattern => #value,));
+            }
+            if arms.is_empty() {
+                // an empty enum can not be matched through a reference
+                quote!(match *self {})
+            } else {
+                quote!(match self { #(#arms)* })
+            }
+        }
+        Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "`Fatality` cannot be derived for unions",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut where_clause =
+        where_clause.cloned().unwrap_or_else(|| syn::parse_quote!(where));
+    for ty in bounds {
+        where_clause.predicates.push(syn::parse_quote!(#ty: ::fatal_error::Fatality));
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::fatal_error::Fatality for #ident #ty_generics #where_clause {
+            fn is_fatal(&self) -> bool { #body }
+        }
+
+        impl #impl_generics ::core::convert::From<#ident #ty_generics>
+            for ::fatal_error::FatalError<#ident #ty_generics> #where_clause
+        {
+            fn from(value: #ident #ty_generics) -> Self {
+                ::fatal_error::Fatality::classify(value)
+            }
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// wraps this error into a [`FatalError`](::fatal_error::FatalError) according to its
+            /// [`Fatality`](::fatal_error::Fatality)
+            pub fn into_fatal_error(self) -> ::fatal_error::FatalError<Self> {
+                ::core::convert::From::from(self)
+            }
+        }
+    })
+}
@@ -0,0 +1,8 @@
+//! [`anyhow`] interoperability preserving the severity of errors
+//!
+//! The severity is recorded by a context layer displayed as `fatal error` or
+//! `non fatal error`, it is found even after adding more context. As any context, the marker
+//! keeps the error reachable by [`anyhow::Error::downcast_ref`](::anyhow::Error::downcast_ref).
+use ::anyhow::{Error, Result};
+
+crate::marker::report_markers!("anyhow", Error, Result, AnyhowResultExt, context);
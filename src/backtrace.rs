@@ -0,0 +1,84 @@
+use crate::{FatalError, Fatality};
+use std::{backtrace::Backtrace, error::Error as StdError};
+
+/// Fatal payload capturing a backtrace where the error became fatal
+///
+/// The backtrace is captured by [`Backtraced::new`] and the [`From`] conversion used by
+/// [`FatalError::escalate`], only with the `backtrace` feature and according to
+/// `RUST_BACKTRACE` and `RUST_LIB_BACKTRACE`, see [`Backtrace::capture`].
+pub struct Backtraced<E> {
+    error:     E,
+    backtrace: Backtrace,
+}
+
+/// captures a backtrace if the `backtrace` feature is enabled
+pub(crate) fn capture() -> Backtrace {
+    #[cfg(feature = "backtrace")]
+    return Backtrace::capture();
+    #[cfg(not(feature = "backtrace"))]
+    return Backtrace::disabled();
+}
+
+/// [`FatalError`] capturing a backtrace when it becomes fatal
+pub type BacktracedError<E> = FatalError<E, Backtraced<E>>;
+
+impl<E> Backtraced<E> {
+    /// wraps the error and captures a backtrace, without the `backtrace` feature
+    /// [`Backtrace::disabled`] is stored instead
+    pub fn new(error: E) -> Self { Backtraced { error, backtrace: capture() } }
+
+    /// return the inner error
+    pub fn inner(&self) -> &E { &self.error }
+
+    /// return the captured backtrace
+    pub fn backtrace(&self) -> &Backtrace { &self.backtrace }
+
+    /// drops the backtrace and return the inner error
+    pub fn into_inner(self) -> E { self.error }
+}
+
+impl<E> From<E> for Backtraced<E> {
+    fn from(error: E) -> Self { Backtraced::new(error) }
+}
+
+impl<E> FatalError<E, Backtraced<E>> {
+    /// creates a fatal error capturing a backtrace
+    pub fn fatal(error: E) -> Self { FatalError::Fatal(Backtraced::new(error)) }
+
+    /// return the backtrace captured when the error became fatal
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        match self {
+            FatalError::Error(_) => None,
+            FatalError::Fatal(x) => Some(x.backtrace()),
+        }
+    }
+
+    /// makes this error non fatal dropping the backtrace
+    pub fn deescalate_backtraced(self) -> Self {
+        self.deescalate_with(Backtraced::into_inner)
+    }
+}
+
+impl<E: std::fmt::Debug> std::fmt::Debug for Backtraced<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.error, f)?;
+        if let std::backtrace::BacktraceStatus::Captured = self.backtrace.status() {
+            write!(f, "\n\nStack backtrace:\n{}", self.backtrace)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for Backtraced<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<E: StdError> StdError for Backtraced<E> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> { self.error.source() }
+}
+
+impl<E: Fatality> Fatality for Backtraced<E> {
+    fn is_fatal(&self) -> bool { self.error.is_fatal() }
+}
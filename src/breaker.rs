@@ -0,0 +1,251 @@
+//! Circuit breaker escalating repeated non fatal errors
+//!
+//! A [`CircuitBreaker`] counts the non fatal errors of the calls it wraps within a sliding
+//! window. Once the threshold is reached the breaker opens: the error that tripped it is
+//! escalated and the following calls fail with [`BreakerError::Open`] without being made.
+//! After the cool-down a single call is let through to probe the dependency, the breaker
+//! closes if it succeeds and opens again otherwise.
+use crate::{
+    clock::{Clock, SystemClock},
+    FatalError,
+};
+use std::{
+    collections::VecDeque,
+    error::Error as StdError,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+/// State of a [`CircuitBreaker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BreakerState {
+    /// calls are made and their non fatal errors counted
+    Closed,
+    /// calls fail without being made
+    Open,
+    /// a single call is let through to probe the dependency
+    HalfOpen,
+}
+
+impl std::fmt::Display for BreakerState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BreakerState::Closed => write!(f, "closed"),
+            BreakerState::Open => write!(f, "open"),
+            BreakerState::HalfOpen => write!(f, "half-open"),
+        }
+    }
+}
+
+/// Fatal error of a call wrapped by a [`CircuitBreaker`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreakerError<E, F = E> {
+    /// the breaker is open, the call was not made
+    Open {
+        /// time left before the breaker lets a probe through
+        retry_after: Duration,
+    },
+    /// the non fatal error that opened the breaker
+    Tripped(E),
+    /// the call failed with a fatal error
+    Fatal(F),
+}
+
+impl<E, F: std::fmt::Display> std::fmt::Display for BreakerError<E, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BreakerError::Open { retry_after } => {
+                write!(f, "circuit breaker open, retry after {retry_after:?}")
+            }
+            BreakerError::Tripped(_) => write!(f, "circuit breaker tripped"),
+            BreakerError::Fatal(x) => std::fmt::Display::fmt(x, f),
+        }
+    }
+}
+
+impl<E: StdError + 'static, F: StdError + 'static> StdError for BreakerError<E, F> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            BreakerError::Open { .. } => None,
+            BreakerError::Tripped(x) => Some(x),
+            BreakerError::Fatal(x) => x.source(),
+        }
+    }
+}
+
+/// Thread safe circuit breaker, see the [module documentation](self)
+///
+/// By default the breaker opens after 5 non fatal errors within 60 seconds and probes after a
+/// cool-down of 30 seconds.
+#[derive(Debug)]
+pub struct CircuitBreaker<C = SystemClock> {
+    threshold: usize,
+    window:    Duration,
+    cool_down: Duration,
+    clock:     C,
+    state:     Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    breaker:  Inner,
+    failures: VecDeque<Instant>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Inner {
+    Closed,
+    Open { since: Instant },
+    HalfOpen { since: Instant },
+}
+
+impl CircuitBreaker {
+    /// creates a breaker with the default configuration
+    pub fn new() -> Self { Self::with_clock(SystemClock) }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self { Self::new() }
+}
+
+impl<C: Clock> CircuitBreaker<C> {
+    /// creates a breaker with the default configuration using the given clock
+    pub fn with_clock(clock: C) -> Self {
+        CircuitBreaker {
+            threshold: 5,
+            window: Duration::from_secs(60),
+            cool_down: Duration::from_secs(30),
+            clock,
+            state: Mutex::new(State {
+                breaker:  Inner::Closed,
+                failures: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// sets the number of non fatal errors within the window opening the breaker, at least 1
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold.max(1);
+        self
+    }
+
+    /// sets the duration of the sliding window in which non fatal errors are counted
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// sets the time the breaker stays open before letting a probe through
+    pub fn cool_down(mut self, cool_down: Duration) -> Self {
+        self.cool_down = cool_down;
+        self
+    }
+
+    /// return the current state of the breaker
+    pub fn state(&self) -> BreakerState {
+        match self.lock().breaker {
+            Inner::Closed => BreakerState::Closed,
+            Inner::Open { .. } => BreakerState::Open,
+            Inner::HalfOpen { .. } => BreakerState::HalfOpen,
+        }
+    }
+
+    /// return the number of non fatal errors counted in the current window
+    pub fn failures(&self) -> usize {
+        let now = self.clock.now();
+        let mut state = self.lock();
+        self.expire(&mut state, now);
+        state.failures.len()
+    }
+
+    /// closes the breaker and forgets the errors counted
+    pub fn reset(&self) {
+        let mut state = self.lock();
+        state.breaker = Inner::Closed;
+        state.failures.clear();
+    }
+
+    /// calls f if the breaker lets it through and records its result
+    ///
+    /// non fatal errors are returned as is unless they open the breaker, in which case they
+    /// are escalated into [`BreakerError::Tripped`]
+    pub fn call<T, E, F, G>(&self, f: G) -> Result<T, FatalError<E, BreakerError<E, F>>>
+    where
+        G: FnOnce() -> Result<T, FatalError<E, F>>,
+    {
+        let probe = self
+            .acquire()
+            .map_err(|x| FatalError::Fatal(BreakerError::Open { retry_after: x }))?;
+        let result = f();
+        let now = self.clock.now();
+        let mut state = self.lock();
+        match result {
+            Ok(x) => {
+                if probe {
+                    state.breaker = Inner::Closed;
+                    state.failures.clear();
+                }
+                Ok(x)
+            }
+            Err(FatalError::Error(x)) => {
+                if probe {
+                    state.breaker = Inner::Open { since: now };
+                    return Err(FatalError::Fatal(BreakerError::Tripped(x)));
+                }
+                state.failures.push_back(now);
+                self.expire(&mut state, now);
+                if matches!(state.breaker, Inner::Closed)
+                    && state.failures.len() >= self.threshold
+                {
+                    state.breaker = Inner::Open { since: now };
+                    state.failures.clear();
+                    Err(FatalError::Fatal(BreakerError::Tripped(x)))
+                } else {
+                    Err(FatalError::Error(x))
+                }
+            }
+            Err(FatalError::Fatal(x)) => {
+                if probe {
+                    state.breaker = Inner::Open { since: now };
+                }
+                Err(FatalError::Fatal(BreakerError::Fatal(x)))
+            }
+        }
+    }
+
+    /// return whether the call is a probe, or the time left before the next probe
+    fn acquire(&self) -> Result<bool, Duration> {
+        let now = self.clock.now();
+        let mut state = self.lock();
+        match state.breaker {
+            Inner::Closed => Ok(false),
+            // a probe that takes longer than the cool-down is considered lost
+            Inner::Open { since } | Inner::HalfOpen { since } => {
+                let elapsed = now.saturating_duration_since(since);
+                if elapsed >= self.cool_down {
+                    state.breaker = Inner::HalfOpen { since: now };
+                    Ok(true)
+                } else if let Inner::HalfOpen { .. } = state.breaker {
+                    Err(Duration::ZERO)
+                } else {
+                    Err(self.cool_down - elapsed)
+                }
+            }
+        }
+    }
+
+    /// drops the errors that left the window
+    fn expire(&self, state: &mut State, now: Instant) {
+        while state
+            .failures
+            .front()
+            .is_some_and(|&x| now.saturating_duration_since(x) >= self.window)
+        {
+            state.failures.pop_front();
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|x| x.into_inner())
+    }
+}
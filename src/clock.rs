@@ -0,0 +1,27 @@
+//! Time source abstraction used by the executors of this crate
+use std::time::{Duration, Instant};
+
+/// Source of time, can be replaced to run executors deterministically
+pub trait Clock {
+    /// return the current instant
+    fn now(&self) -> Instant;
+
+    /// blocks the current thread for the given duration
+    fn sleep(&self, duration: Duration);
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Instant { (**self).now() }
+
+    fn sleep(&self, duration: Duration) { (**self).sleep(duration) }
+}
+
+/// [`Clock`] backed by [`Instant::now`] and [`std::thread::sleep`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant { Instant::now() }
+
+    fn sleep(&self, duration: Duration) { std::thread::sleep(duration) }
+}
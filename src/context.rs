@@ -0,0 +1,51 @@
+use crate::{FatalError, Fatality};
+use core::error::Error as StdError;
+
+/// Error layer attaching a context to an inner error
+///
+/// Displays the context only, the alternate mode (`{:#}`) renders the whole stack as
+/// `context: inner`, the inner error is exposed as the source of the layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Context<C, E> {
+    context: C,
+    error:   E,
+}
+
+/// [`FatalError`] whose inner errors carry a [`Context`]
+pub type ContextError<C, E, F = E> = FatalError<Context<C, E>, Context<C, F>>;
+
+impl<C, E> Context<C, E> {
+    /// attaches the context to the error
+    pub fn new(context: C, error: E) -> Self { Context { context, error } }
+
+    /// return the context
+    pub fn context(&self) -> &C { &self.context }
+
+    /// return the inner error
+    pub fn inner(&self) -> &E { &self.error }
+
+    /// drops the context and return the inner error
+    pub fn into_inner(self) -> E { self.error }
+}
+
+impl<C: core::fmt::Display, E: core::fmt::Display> core::fmt::Display for Context<C, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            write!(f, "{}: {:#}", self.context, self.error)
+        } else {
+            write!(f, "{}", self.context)
+        }
+    }
+}
+
+impl<C, E> StdError for Context<C, E>
+where
+    C: core::fmt::Display + core::fmt::Debug,
+    E: StdError + 'static,
+{
+    fn source(&self) -> Option<&(dyn StdError + 'static)> { Some(&self.error) }
+}
+
+impl<C, E: Fatality> Fatality for Context<C, E> {
+    fn is_fatal(&self) -> bool { self.error.is_fatal() }
+}
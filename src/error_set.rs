@@ -0,0 +1,151 @@
+use crate::{FatalError, Fatality, Severity};
+use alloc::vec::Vec;
+use core::{error::Error as StdError, ops::ControlFlow};
+
+/// Collection of errors accumulating non fatal errors and stopping on fatal ones
+///
+/// The set becomes fatal when a fatal error is pushed or when the number of non fatal errors
+/// exceeds the threshold given to [`ErrorSet::with_threshold`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorSet<E, F = E> {
+    entries:     Vec<FatalError<E, F>>,
+    recoverable: usize,
+    threshold:   Option<usize>,
+}
+
+impl<E, F> ErrorSet<E, F> {
+    /// creates an empty set without threshold
+    pub fn new() -> Self {
+        ErrorSet { entries: Vec::new(), recoverable: 0, threshold: None }
+    }
+
+    /// creates an empty set that becomes fatal once more than `threshold` non fatal errors
+    /// are pushed
+    pub fn with_threshold(threshold: usize) -> Self {
+        ErrorSet { threshold: Some(threshold), ..Self::new() }
+    }
+
+    /// adds an error to the set
+    ///
+    /// return [`ControlFlow::Break`] if the set is fatal after adding the error
+    pub fn push(&mut self, error: FatalError<E, F>) -> ControlFlow<()> {
+        if error.is_error() {
+            self.recoverable += 1;
+        }
+        self.entries.push(error);
+        if self.is_fatal() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+
+    /// return true if the set holds a fatal error or exceeded its threshold
+    pub fn is_fatal(&self) -> bool {
+        self.fatal_count() > 0 || self.threshold.is_some_and(|x| self.recoverable > x)
+    }
+
+    /// return true if the set is empty
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// return the number of errors in the set
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    /// return the number of non fatal errors in the set
+    pub fn recoverable_count(&self) -> usize { self.recoverable }
+
+    /// return the number of fatal errors in the set
+    pub fn fatal_count(&self) -> usize { self.entries.len() - self.recoverable }
+
+    /// iterates over the errors in the order they were pushed
+    pub fn iter(&self) -> core::slice::Iter<'_, FatalError<E, F>> { self.entries.iter() }
+
+    /// iterates over the non fatal errors
+    pub fn errors(&self) -> impl Iterator<Item = &E> {
+        self.entries.iter().filter_map(|x| x.as_ref().fatality().ok())
+    }
+
+    /// iterates over the fatal errors
+    pub fn fatals(&self) -> impl Iterator<Item = &F> {
+        self.entries.iter().filter_map(|x| x.as_ref().recover().err())
+    }
+
+    /// transforms the set into the errors it holds
+    pub fn into_vec(self) -> Vec<FatalError<E, F>> { self.entries }
+
+    /// return `Ok(())` if the set is empty, otherwise the set wrapped according to
+    /// [`ErrorSet::is_fatal`]
+    pub fn finish(self) -> Result<(), FatalError<Self>> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.classify())
+        }
+    }
+}
+
+impl<E, F> Default for ErrorSet<E, F> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<E, F> Extend<FatalError<E, F>> for ErrorSet<E, F> {
+    fn extend<I: IntoIterator<Item = FatalError<E, F>>>(&mut self, iter: I) {
+        for x in iter {
+            let _ = self.push(x);
+        }
+    }
+}
+
+impl<E, F> FromIterator<FatalError<E, F>> for ErrorSet<E, F> {
+    fn from_iter<I: IntoIterator<Item = FatalError<E, F>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<E, F> IntoIterator for ErrorSet<E, F> {
+    type IntoIter = alloc::vec::IntoIter<FatalError<E, F>>;
+    type Item = FatalError<E, F>;
+
+    fn into_iter(self) -> Self::IntoIter { self.entries.into_iter() }
+}
+
+impl<'a, E, F> IntoIterator for &'a ErrorSet<E, F> {
+    type IntoIter = core::slice::Iter<'a, FatalError<E, F>>;
+    type Item = &'a FatalError<E, F>;
+
+    fn into_iter(self) -> Self::IntoIter { self.entries.iter() }
+}
+
+impl<E, F> Fatality for ErrorSet<E, F> {
+    fn is_fatal(&self) -> bool { ErrorSet::is_fatal(self) }
+}
+
+impl<E: core::fmt::Display, F: core::fmt::Display> core::fmt::Display for ErrorSet<E, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} error(s)", self.entries.len())?;
+        for x in &self.entries {
+            write!(f, "\n- {x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: StdError + 'static, F: StdError + 'static> StdError for ErrorSet<E, F> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.entries
+            .iter()
+            .find(|x| x.is_fatal())
+            .or_else(|| self.entries.first())
+            .map(|x| x as &(dyn StdError + 'static))
+    }
+
+    // the set carries its own severity, a set past its threshold is fatal even though its
+    // sources are not, see `find_fatality`
+    #[allow(deprecated)]
+    fn description(&self) -> &str {
+        let severity = if self.is_fatal() { Severity::Fatal } else { Severity::Error };
+        severity.sentinel()
+    }
+}
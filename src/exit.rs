@@ -0,0 +1,105 @@
+//! Exit codes for programs returning a [`FatalError`] from `main`
+//!
+//! `main` can return an [`ExitReport`], on failure the error and its sources are printed to
+//! the standard error and the process exits with the code given by [`FatalExitCode`], by
+//! default [`EX_TEMPFAIL`] for non fatal errors and [`EX_SOFTWARE`] for fatal errors.
+//!
+//! ```no_run
+//! use fatal_error::{exit::ExitReport, FatalError};
+//!
+//! fn run() -> Result<(), FatalError<std::io::Error>> { Ok(()) }
+//!
+//! fn main() -> ExitReport<std::io::Error> { run().into() }
+//! ```
+use crate::{Backtraced, Context, FatalError, NeverErr, Severity, Tracked};
+use std::{
+    error::Error as StdError,
+    io::{self, Write},
+    process::{ExitCode, Termination},
+};
+
+/// temporary failure, the user is invited to retry later (`sysexits.h`)
+pub const EX_TEMPFAIL: u8 = 75;
+
+/// internal software error (`sysexits.h`)
+pub const EX_SOFTWARE: u8 = 70;
+
+/// Exit code of a program failing with an error
+pub trait FatalExitCode {
+    /// return the exit code of the error with the given severity
+    fn exit_code(&self, severity: Severity) -> u8 {
+        match severity {
+            Severity::Error => EX_TEMPFAIL,
+            Severity::Fatal => EX_SOFTWARE,
+        }
+    }
+}
+
+impl FatalExitCode for io::Error {}
+
+impl FatalExitCode for NeverErr {}
+
+impl FatalExitCode for Box<dyn StdError> {}
+
+impl FatalExitCode for Box<dyn StdError + Send + Sync> {}
+
+impl<C, E: FatalExitCode> FatalExitCode for Context<C, E> {
+    fn exit_code(&self, severity: Severity) -> u8 { self.inner().exit_code(severity) }
+}
+
+impl<E: FatalExitCode> FatalExitCode for Backtraced<E> {
+    fn exit_code(&self, severity: Severity) -> u8 { self.inner().exit_code(severity) }
+}
+
+impl<E: FatalExitCode> FatalExitCode for Tracked<E> {
+    fn exit_code(&self, severity: Severity) -> u8 { self.inner().exit_code(severity) }
+}
+
+impl<E: FatalExitCode, F: FatalExitCode> FatalExitCode for FatalError<E, F> {
+    fn exit_code(&self, _: Severity) -> u8 {
+        match self {
+            FatalError::Error(x) => x.exit_code(Severity::Error),
+            FatalError::Fatal(x) => x.exit_code(Severity::Fatal),
+        }
+    }
+}
+
+/// Result of `main` exiting with the code of its error, see [`FatalExitCode`]
+#[derive(Debug)]
+pub struct ExitReport<E, F = E>(Result<(), FatalError<E, F>>);
+
+impl<E, F> ExitReport<E, F> {
+    /// wraps the result of the program
+    pub fn new(result: Result<(), FatalError<E, F>>) -> Self { ExitReport(result) }
+
+    /// return the result of the program
+    pub fn into_result(self) -> Result<(), FatalError<E, F>> { self.0 }
+}
+
+impl<E, F> From<Result<(), FatalError<E, F>>> for ExitReport<E, F> {
+    fn from(value: Result<(), FatalError<E, F>>) -> Self { ExitReport(value) }
+}
+
+impl<E, F> From<FatalError<E, F>> for ExitReport<E, F> {
+    fn from(value: FatalError<E, F>) -> Self { ExitReport(Err(value)) }
+}
+
+impl<E, F> Termination for ExitReport<E, F>
+where
+    E: StdError + FatalExitCode + 'static,
+    F: StdError + FatalExitCode + 'static,
+{
+    fn report(self) -> ExitCode {
+        let Err(error) = self.0 else { return ExitCode::SUCCESS };
+        let mut stderr = io::stderr().lock();
+        let _ = writeln!(stderr, "{error}");
+        let mut sources = error.chain().skip(1).enumerate().peekable();
+        if sources.peek().is_some() {
+            let _ = writeln!(stderr, "\nCaused by:");
+            for (i, x) in sources {
+                let _ = writeln!(stderr, "    {i}: {x}");
+            }
+        }
+        ExitCode::from(error.exit_code(error.severity()))
+    }
+}
@@ -0,0 +1,8 @@
+//! [`eyre`] interoperability preserving the severity of errors
+//!
+//! The severity is recorded by a context layer displayed as `fatal error` or
+//! `non fatal error`, it is found even after adding more context. As any context, the marker
+//! keeps the error reachable by [`eyre::Report::downcast_ref`](::eyre::Report::downcast_ref).
+use ::eyre::{Report, Result};
+
+crate::marker::report_markers!("eyre", Report, Result, EyreResultExt, wrap_err);
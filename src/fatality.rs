@@ -0,0 +1,181 @@
+use crate::{FatalError, NeverErr};
+#[cfg(feature = "alloc")]
+use alloc::{
+    boxed::Box,
+    rc::Rc,
+    string::{FromUtf16Error, FromUtf8Error},
+    sync::Arc,
+};
+use core::{
+    cell::BorrowError,
+    char::{CharTryFromError, ParseCharError},
+    convert::Infallible,
+    error::Error as StdError,
+    net::AddrParseError,
+    num::{ParseFloatError, ParseIntError, TryFromIntError},
+    str::{ParseBoolError, Utf8Error},
+};
+#[cfg(feature = "std")]
+use std::{
+    io,
+    sync::{
+        mpsc::{RecvError, RecvTimeoutError, SendError, TryRecvError, TrySendError},
+        PoisonError, TryLockError,
+    },
+    time::SystemTimeError,
+};
+
+/// Errors that know whether they are fatal
+///
+/// Errors caused by invalid input or transient conditions are non fatal, errors caused by a
+/// broken state (disconnected channels, poisoned locks...) are fatal.
+pub trait Fatality {
+    /// return true if this error is fatal
+    fn is_fatal(&self) -> bool;
+
+    /// wraps this error into a [`FatalError::Fatal`] if it is fatal, otherwise into a
+    /// [`FatalError::Error`]
+    fn classify(self) -> FatalError<Self>
+    where
+        Self: Sized,
+    {
+        if self.is_fatal() {
+            FatalError::Fatal(self)
+        } else {
+            FatalError::Error(self)
+        }
+    }
+}
+
+impl<E, F> Fatality for FatalError<E, F> {
+    fn is_fatal(&self) -> bool { FatalError::is_fatal(self) }
+}
+
+impl Fatality for NeverErr {
+    fn is_fatal(&self) -> bool { match *self {} }
+}
+
+impl Fatality for Infallible {
+    fn is_fatal(&self) -> bool { match *self {} }
+}
+
+impl<T: Fatality + ?Sized> Fatality for &T {
+    fn is_fatal(&self) -> bool { (**self).is_fatal() }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Fatality + ?Sized> Fatality for Box<T> {
+    fn is_fatal(&self) -> bool { (**self).is_fatal() }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Fatality + ?Sized> Fatality for Rc<T> {
+    fn is_fatal(&self) -> bool { (**self).is_fatal() }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Fatality + ?Sized> Fatality for Arc<T> {
+    fn is_fatal(&self) -> bool { (**self).is_fatal() }
+}
+
+/// Looks for a [`FatalError`] layer in the chain of the error, then downcasts the error to the
+/// types known by this crate, unknown errors are non fatal
+fn dyn_is_fatal(error: &(dyn StdError + 'static)) -> bool {
+    if let Some(x) = crate::find_fatality(error) {
+        return x;
+    }
+    #[cfg(feature = "std")]
+    {
+        macro_rules! downcast {
+            ($($ty:ty),* $(,)?) => {
+                $(
+                    if let Some(x) = error.downcast_ref::<$ty>() {
+                        return x.is_fatal();
+                    }
+                )*
+            };
+        }
+        downcast!(io::Error, RecvError, RecvTimeoutError, TryRecvError);
+    }
+    false
+}
+
+impl Fatality for dyn StdError {
+    fn is_fatal(&self) -> bool { dyn_is_fatal(self) }
+}
+
+impl Fatality for dyn StdError + Send {
+    fn is_fatal(&self) -> bool { dyn_is_fatal(self) }
+}
+
+impl Fatality for dyn StdError + Send + Sync {
+    fn is_fatal(&self) -> bool { dyn_is_fatal(self) }
+}
+
+#[cfg(feature = "std")]
+impl Fatality for io::Error {
+    fn is_fatal(&self) -> bool { crate::io::default_severity(self.kind()).is_fatal() }
+}
+
+macro_rules! non_fatal {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Fatality for $ty {
+                fn is_fatal(&self) -> bool { false }
+            }
+        )*
+    };
+}
+
+non_fatal!(
+    AddrParseError,
+    BorrowError,
+    CharTryFromError,
+    ParseBoolError,
+    ParseCharError,
+    ParseFloatError,
+    ParseIntError,
+    TryFromIntError,
+    Utf8Error,
+);
+
+#[cfg(feature = "alloc")]
+non_fatal!(FromUtf16Error, FromUtf8Error);
+
+#[cfg(feature = "std")]
+non_fatal!(SystemTimeError);
+
+#[cfg(feature = "std")]
+impl Fatality for RecvError {
+    fn is_fatal(&self) -> bool { true }
+}
+
+#[cfg(feature = "std")]
+impl Fatality for RecvTimeoutError {
+    fn is_fatal(&self) -> bool { matches!(self, RecvTimeoutError::Disconnected) }
+}
+
+#[cfg(feature = "std")]
+impl Fatality for TryRecvError {
+    fn is_fatal(&self) -> bool { matches!(self, TryRecvError::Disconnected) }
+}
+
+#[cfg(feature = "std")]
+impl<T> Fatality for SendError<T> {
+    fn is_fatal(&self) -> bool { true }
+}
+
+#[cfg(feature = "std")]
+impl<T> Fatality for TrySendError<T> {
+    fn is_fatal(&self) -> bool { matches!(self, TrySendError::Disconnected(_)) }
+}
+
+#[cfg(feature = "std")]
+impl<T> Fatality for PoisonError<T> {
+    fn is_fatal(&self) -> bool { true }
+}
+
+#[cfg(feature = "std")]
+impl<T> Fatality for TryLockError<T> {
+    fn is_fatal(&self) -> bool { matches!(self, TryLockError::Poisoned(_)) }
+}
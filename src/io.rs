@@ -0,0 +1,146 @@
+//! Classification of [`std::io::Error`]
+//!
+//! Errors that may disappear by retrying the operation are non fatal, the others are fatal.
+//! The default classification of every [`ErrorKind`] is:
+//!
+//! | Kind                                                          | Severity |
+//! |---------------------------------------------------------------|----------|
+//! | [`Interrupted`](ErrorKind::Interrupted)                       | error    |
+//! | [`WouldBlock`](ErrorKind::WouldBlock)                         | error    |
+//! | [`TimedOut`](ErrorKind::TimedOut)                             | error    |
+//! | [`ConnectionRefused`](ErrorKind::ConnectionRefused)           | error    |
+//! | [`ConnectionReset`](ErrorKind::ConnectionReset)               | error    |
+//! | [`ConnectionAborted`](ErrorKind::ConnectionAborted)           | error    |
+//! | [`NotConnected`](ErrorKind::NotConnected)                     | error    |
+//! | [`BrokenPipe`](ErrorKind::BrokenPipe)                         | error    |
+//! | [`HostUnreachable`](ErrorKind::HostUnreachable)               | error    |
+//! | [`NetworkUnreachable`](ErrorKind::NetworkUnreachable)         | error    |
+//! | [`NetworkDown`](ErrorKind::NetworkDown)                       | error    |
+//! | [`AddrInUse`](ErrorKind::AddrInUse)                           | error    |
+//! | [`AddrNotAvailable`](ErrorKind::AddrNotAvailable)             | error    |
+//! | [`ResourceBusy`](ErrorKind::ResourceBusy)                     | error    |
+//! | [`ExecutableFileBusy`](ErrorKind::ExecutableFileBusy)         | error    |
+//! | [`StaleNetworkFileHandle`](ErrorKind::StaleNetworkFileHandle) | error    |
+//! | [`Deadlock`](ErrorKind::Deadlock)                             | error    |
+//! | [`NotFound`](ErrorKind::NotFound)                             | error    |
+//! | [`AlreadyExists`](ErrorKind::AlreadyExists)                   | error    |
+//! | [`DirectoryNotEmpty`](ErrorKind::DirectoryNotEmpty)           | error    |
+//! | [`PermissionDenied`](ErrorKind::PermissionDenied)             | fatal    |
+//! | [`InvalidInput`](ErrorKind::InvalidInput)                     | fatal    |
+//! | [`InvalidData`](ErrorKind::InvalidData)                       | fatal    |
+//! | [`UnexpectedEof`](ErrorKind::UnexpectedEof)                   | fatal    |
+//! | [`WriteZero`](ErrorKind::WriteZero)                           | fatal    |
+//! | [`Unsupported`](ErrorKind::Unsupported)                       | fatal    |
+//! | [`OutOfMemory`](ErrorKind::OutOfMemory)                       | fatal    |
+//! | [`NotADirectory`](ErrorKind::NotADirectory)                   | fatal    |
+//! | [`IsADirectory`](ErrorKind::IsADirectory)                     | fatal    |
+//! | [`ReadOnlyFilesystem`](ErrorKind::ReadOnlyFilesystem)         | fatal    |
+//! | [`StorageFull`](ErrorKind::StorageFull)                       | fatal    |
+//! | [`QuotaExceeded`](ErrorKind::QuotaExceeded)                   | fatal    |
+//! | [`FileTooLarge`](ErrorKind::FileTooLarge)                     | fatal    |
+//! | [`NotSeekable`](ErrorKind::NotSeekable)                       | fatal    |
+//! | [`CrossesDevices`](ErrorKind::CrossesDevices)                 | fatal    |
+//! | [`TooManyLinks`](ErrorKind::TooManyLinks)                     | fatal    |
+//! | [`InvalidFilename`](ErrorKind::InvalidFilename)               | fatal    |
+//! | [`ArgumentListTooLong`](ErrorKind::ArgumentListTooLong)       | fatal    |
+//! | [`Other`](ErrorKind::Other) and any other kind                | fatal    |
+//!
+//! Raw OS errors are classified by the kind std maps them to, see
+//! [`std::io::Error::from_raw_os_error`].
+use crate::{FatalError, Severity};
+use std::io::{Error, ErrorKind};
+
+/// return the default severity of an error kind, see the [module documentation](self)
+pub fn default_severity(kind: ErrorKind) -> Severity {
+    match kind {
+        ErrorKind::Interrupted
+        | ErrorKind::WouldBlock
+        | ErrorKind::TimedOut
+        | ErrorKind::ConnectionRefused
+        | ErrorKind::ConnectionReset
+        | ErrorKind::ConnectionAborted
+        | ErrorKind::NotConnected
+        | ErrorKind::BrokenPipe
+        | ErrorKind::HostUnreachable
+        | ErrorKind::NetworkUnreachable
+        | ErrorKind::NetworkDown
+        | ErrorKind::AddrInUse
+        | ErrorKind::AddrNotAvailable
+        | ErrorKind::ResourceBusy
+        | ErrorKind::ExecutableFileBusy
+        | ErrorKind::StaleNetworkFileHandle
+        | ErrorKind::Deadlock
+        | ErrorKind::NotFound
+        | ErrorKind::AlreadyExists
+        | ErrorKind::DirectoryNotEmpty => Severity::Error,
+        _ => Severity::Fatal,
+    }
+}
+
+/// Classifies [`std::io::Error`] with the default table and user overrides
+///
+/// Overrides of raw OS error codes take precedence over overrides of kinds, which take
+/// precedence over [`default_severity`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IoClassifier {
+    kinds:     Vec<(ErrorKind, Severity)>,
+    os_errors: Vec<(i32, Severity)>,
+}
+
+impl IoClassifier {
+    /// creates a classifier using the default table
+    pub fn new() -> Self { Self::default() }
+
+    /// overrides the severity of an error kind
+    pub fn kind(mut self, kind: ErrorKind, severity: Severity) -> Self {
+        self.kinds.retain(|(x, _)| *x != kind);
+        self.kinds.push((kind, severity));
+        self
+    }
+
+    /// overrides the severity of a raw OS error code
+    pub fn os_error(mut self, code: i32, severity: Severity) -> Self {
+        self.os_errors.retain(|(x, _)| *x != code);
+        self.os_errors.push((code, severity));
+        self
+    }
+
+    /// return the severity of an error kind
+    pub fn kind_severity(&self, kind: ErrorKind) -> Severity {
+        self.kinds
+            .iter()
+            .find(|(x, _)| *x == kind)
+            .map_or_else(|| default_severity(kind), |(_, x)| *x)
+    }
+
+    /// return the severity of a raw OS error code
+    pub fn os_error_severity(&self, code: i32) -> Severity {
+        match self.os_errors.iter().find(|(x, _)| *x == code) {
+            Some((_, x)) => *x,
+            None => self.kind_severity(Error::from_raw_os_error(code).kind()),
+        }
+    }
+
+    /// return the severity of an error
+    pub fn severity(&self, error: &Error) -> Severity {
+        match error.raw_os_error() {
+            Some(x) => self.os_error_severity(x),
+            None => self.kind_severity(error.kind()),
+        }
+    }
+
+    /// wraps an error according to its severity
+    pub fn classify(&self, error: Error) -> FatalError<Error> {
+        self.severity(&error).wrap(error)
+    }
+
+    /// creates an error from a kind and wraps it according to its severity
+    pub fn classify_kind(&self, kind: ErrorKind) -> FatalError<Error> {
+        self.classify(Error::from(kind))
+    }
+
+    /// creates an error from a raw OS error code and wraps it according to its severity
+    pub fn classify_os_error(&self, code: i32) -> FatalError<Error> {
+        self.classify(Error::from_raw_os_error(code))
+    }
+}
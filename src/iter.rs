@@ -0,0 +1,108 @@
+//! Iterator adapters skipping non fatal errors and stopping on fatal ones
+use crate::FatalError;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Fatality aware adapters for iterators over `Result<T, FatalError<E, F>>`
+pub trait FatalIteratorExt<T, E, F>:
+    Iterator<Item = Result<T, FatalError<E, F>>> + Sized
+{
+    /// yields the successful values, collects non fatal errors and stops on the first fatal
+    /// error, see [`UntilFatal`]
+    fn until_fatal(self) -> UntilFatal<Self, E, F> {
+        UntilFatal { iter: self, errors: Vec::new(), fatal: None }
+    }
+
+    /// splits the values and non fatal errors until the first fatal error, which is returned
+    /// if any
+    fn partition_fatality(self) -> (Vec<T>, Vec<E>, Option<F>) {
+        let mut iter = self.until_fatal();
+        let values = iter.by_ref().collect();
+        let (errors, fatal) = iter.into_parts();
+        (values, errors, fatal)
+    }
+
+    /// collects the values and non fatal errors, stops and returns the first fatal error if
+    /// any
+    fn collect_until_fatal<C>(self) -> Result<(C, Vec<E>), F>
+    where
+        C: FromIterator<T>,
+    {
+        let mut iter = self.until_fatal();
+        let values = iter.by_ref().collect();
+        iter.finish().map(|x| (values, x))
+    }
+}
+
+impl<I, T, E, F> FatalIteratorExt<T, E, F> for I where
+    I: Iterator<Item = Result<T, FatalError<E, F>>>
+{
+}
+
+/// Iterator yielding successful values until the first fatal error
+///
+/// Non fatal errors are collected on the side, once the iterator is exhausted use
+/// [`UntilFatal::finish`] to get them along with the fatal error.
+#[derive(Debug, Clone)]
+pub struct UntilFatal<I, E, F> {
+    iter:   I,
+    errors: Vec<E>,
+    fatal:  Option<F>,
+}
+
+impl<I, E, F> UntilFatal<I, E, F> {
+    /// return the non fatal errors collected so far
+    pub fn errors(&self) -> &[E] { &self.errors }
+
+    /// takes the non fatal errors collected so far
+    pub fn take_errors(&mut self) -> Vec<E> { core::mem::take(&mut self.errors) }
+
+    /// return the fatal error that stopped the iteration if any
+    pub fn fatal(&self) -> Option<&F> { self.fatal.as_ref() }
+
+    /// return the non fatal errors collected and the fatal error if any
+    pub fn into_parts(self) -> (Vec<E>, Option<F>) { (self.errors, self.fatal) }
+
+    /// return the fatal error if any, otherwise the non fatal errors collected
+    pub fn finish(self) -> Result<Vec<E>, F> {
+        match self.fatal {
+            Some(x) => Err(x),
+            None => Ok(self.errors),
+        }
+    }
+}
+
+impl<I, T, E, F> Iterator for UntilFatal<I, E, F>
+where
+    I: Iterator<Item = Result<T, FatalError<E, F>>>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.fatal.is_some() {
+            return None;
+        }
+        loop {
+            match self.iter.next()? {
+                Ok(x) => return Some(x),
+                Err(FatalError::Error(x)) => self.errors.push(x),
+                Err(FatalError::Fatal(x)) => {
+                    self.fatal = Some(x);
+                    return None;
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.fatal {
+            Some(_) => (0, Some(0)),
+            None => (0, self.iter.size_hint().1),
+        }
+    }
+}
+
+impl<I, T, E, F> FusedIterator for UntilFatal<I, E, F> where
+    I: FusedIterator<Item = Result<T, FatalError<E, F>>>
+{
+}
@@ -1,56 +1,232 @@
 //! Utility crate for differentiating fatal and non fatal errors
-use std::error::Error as StdError;
+//!
+//! The crate is `no_std` without the default `std` feature, the `alloc` feature enables the
+//! types that need an allocator.
+#![cfg_attr(not(feature = "std"), no_std)]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::error::Error as StdError;
+
+#[cfg(feature = "anyhow")]
+pub mod anyhow;
+#[cfg(feature = "std")]
+mod backtrace;
+#[cfg(feature = "std")]
+pub mod breaker;
+#[cfg(feature = "std")]
+pub mod clock;
+mod context;
+#[cfg(feature = "alloc")]
+mod error_set;
+#[cfg(feature = "std")]
+pub mod exit;
+#[cfg(feature = "eyre")]
+pub mod eyre;
+mod fatality;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "alloc")]
+pub mod iter;
+#[cfg(feature = "log")]
+pub mod log;
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+mod marker;
+mod outcome;
+#[cfg(feature = "std")]
+pub mod panic;
+#[cfg(feature = "policy")]
+pub mod policy;
+mod result;
+#[cfg(feature = "std")]
+pub mod retry;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "futures")]
+pub mod stream;
+#[cfg(feature = "std")]
+pub mod supervisor;
+#[cfg(feature = "tracing")]
+pub mod tracing;
+#[cfg(feature = "alloc")]
+mod tracked;
+
+#[cfg(feature = "std")]
+pub use backtrace::{Backtraced, BacktracedError};
+pub use context::{Context, ContextError};
+#[cfg(feature = "alloc")]
+pub use error_set::ErrorSet;
+#[cfg(feature = "derive")]
+pub use fatal_error_derive::Fatality;
+pub use fatality::Fatality;
+#[cfg(feature = "alloc")]
+pub use iter::FatalIteratorExt;
+pub use outcome::{FromFatal, Outcome};
+#[cfg(feature = "std")]
+pub use panic::{catch, PanicError};
+pub use result::{FatalResultExt, IntoFatalResultExt};
+#[cfg(feature = "futures")]
+pub use stream::FatalStreamExt;
+#[cfg(feature = "alloc")]
+pub use tracked::{Tracked, TrackedError, Transition};
 
 /// An error that can never happend
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum NeverErr {}
 
-impl std::fmt::Display for NeverErr {
-    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { match *self {} }
+impl core::fmt::Display for NeverErr {
+    fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {}
+    }
+}
+
+impl core::error::Error for NeverErr {}
+
+/// Severity of an error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Severity {
+    /// non fatal error
+    Error,
+    /// fatal error
+    Fatal,
 }
 
-impl std::error::Error for NeverErr {}
+impl Severity {
+    /// return true if the severity is fatal
+    pub fn is_fatal(self) -> bool { self == Severity::Fatal }
+
+    /// wraps the error into the [`FatalError`] variant matching this severity
+    pub fn wrap<E>(self, error: E) -> FatalError<E> {
+        match self {
+            Severity::Error => FatalError::Error(error),
+            Severity::Fatal => FatalError::Fatal(error),
+        }
+    }
+
+    /// description of the error layers carrying this severity, see [`find_fatality`]
+    pub(crate) fn sentinel(self) -> &'static str {
+        match self {
+            Severity::Error => "fatal_error::FatalError::Error",
+            Severity::Fatal => "fatal_error::FatalError::Fatal",
+        }
+    }
+}
+
+impl core::fmt::Display for Severity {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Fatal => write!(f, "fatal"),
+        }
+    }
+}
 
 /// Error type
+///
+/// The fatal variant may hold a different payload than the non fatal one, for example to
+/// carry more context, it defaults to the type of the non fatal error.
+///
+/// As an [`Error`](core::error::Error) its source is the inner error and its deprecated
+/// `description` returns a marker of its severity, which [`find_fatality`] looks for to
+/// classify the error once its concrete type is erased, for example in a
+/// `Box<dyn Error>` or the source chain of another error. The marker is an implementation
+/// detail and may change, use [`find_fatality`] instead of comparing it.
+///
+/// With the `serde` feature it is represented as `{"severity": "error", "error": ...}` or
+/// `{"severity": "fatal", "error": ...}`, see `serde::externally_tagged` for an alternative
+/// representation.
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub enum FatalError<E> {
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(tag = "severity", content = "error", rename_all = "lowercase")
+)]
+pub enum FatalError<E, F = E> {
     /// Error
     Error(E),
     /// Fatal error
-    Fatal(E),
+    Fatal(F),
 }
 
-impl<E> FatalError<E> {
+impl<E, F> FatalError<E, F> {
     /// return true if this error non is fatal
     pub fn is_error(&self) -> bool { matches!(self, FatalError::Error(_)) }
 
     /// return true if this error is fatal
     pub fn is_fatal(&self) -> bool { matches!(self, FatalError::Fatal(_)) }
 
-    /// transforms the error into it's inner type
-    pub fn into_inner(self) -> E {
+    /// return the severity of this error
+    pub fn severity(&self) -> Severity {
         match self {
-            FatalError::Error(x) => x,
-            FatalError::Fatal(x) => x,
+            FatalError::Error(_) => Severity::Error,
+            FatalError::Fatal(_) => Severity::Fatal,
         }
     }
 
-    /// applies f to the inner error preserving the [`FatalError::Error`] or [`FatalError::Fatal`] state
-    pub fn map<E2, F>(self, f: F) -> FatalError<E2>
+    /// converts from `&FatalError<E, F>` to `FatalError<&E, &F>`
+    pub fn as_ref(&self) -> FatalError<&E, &F> {
+        match self {
+            FatalError::Error(x) => FatalError::Error(x),
+            FatalError::Fatal(x) => FatalError::Fatal(x),
+        }
+    }
+
+    /// applies `on_error` to a non fatal error or `on_fatal` to a fatal error preserving the
+    /// [`FatalError::Error`] or [`FatalError::Fatal`] state
+    pub fn map_both<E2, F2, G, H>(self, on_error: G, on_fatal: H) -> FatalError<E2, F2>
     where
-        F: FnOnce(E) -> E2,
+        G: FnOnce(E) -> E2,
+        H: FnOnce(F) -> F2,
     {
         match self {
-            FatalError::Error(x) => FatalError::Error(f(x)),
-            FatalError::Fatal(x) => FatalError::Fatal(f(x)),
+            FatalError::Error(x) => FatalError::Error(on_error(x)),
+            FatalError::Fatal(x) => FatalError::Fatal(on_fatal(x)),
         }
     }
 
     /// Makes this error fatal
-    pub fn escalate(self) -> Self { FatalError::Fatal(self.into_inner()) }
+    pub fn escalate(self) -> Self
+    where
+        E: Into<F>,
+    {
+        self.escalate_with(Into::into)
+    }
 
     /// Makes this error non fatal
-    pub fn deescalate(self) -> Self { FatalError::Error(self.into_inner()) }
+    pub fn deescalate(self) -> Self
+    where
+        F: Into<E>,
+    {
+        self.deescalate_with(Into::into)
+    }
+
+    /// Makes this error fatal converting a non fatal error with the given closure
+    pub fn escalate_with<G>(self, f: G) -> Self
+    where
+        G: FnOnce(E) -> F,
+    {
+        match self {
+            FatalError::Error(x) => FatalError::Fatal(f(x)),
+            x @ FatalError::Fatal(_) => x,
+        }
+    }
+
+    /// Makes this error non fatal converting a fatal error with the given closure
+    pub fn deescalate_with<G>(self, f: G) -> Self
+    where
+        G: FnOnce(F) -> E,
+    {
+        match self {
+            x @ FatalError::Error(_) => x,
+            FatalError::Fatal(x) => FatalError::Error(f(x)),
+        }
+    }
 
     /// Return `Ok(E)` if the error is non fatal else `Err(Self)` is returned
     pub fn fatality(self) -> Result<E, Self> {
@@ -60,18 +236,27 @@ impl<E> FatalError<E> {
         }
     }
 
-    /// return `Err(E)` if the error is fatal otherwise `Ok(E)` is returned
-    pub fn recover(self) -> Result<E, E> {
+    /// return `Err(F)` if the error is fatal otherwise `Ok(E)` is returned
+    pub fn recover(self) -> Result<E, F> {
         match self {
             FatalError::Error(x) => Ok(x),
             FatalError::Fatal(x) => Err(x),
         }
     }
 
+    /// wraps the inner error in a [`Context`] layer preserving the [`FatalError::Error`] or
+    /// [`FatalError::Fatal`] state
+    pub fn context<C>(self, context: C) -> ContextError<C, E, F> {
+        match self {
+            FatalError::Error(x) => FatalError::Error(Context::new(context, x)),
+            FatalError::Fatal(x) => FatalError::Fatal(Context::new(context, x)),
+        }
+    }
+
     /// recover a non fatal error with the given closure
-    pub fn map_error<T, F>(self, f: F) -> Result<T, Self>
+    pub fn map_error<T, G>(self, f: G) -> Result<T, Self>
     where
-        F: FnOnce(E) -> Result<T, Self>,
+        G: FnOnce(E) -> Result<T, Self>,
     {
         match self {
             FatalError::Error(x) => f(x),
@@ -80,15 +265,36 @@ impl<E> FatalError<E> {
     }
 
     /// recover from a fatal error with the given closure
-    pub fn map_fatal<T, F>(self, f: F) -> Result<T, Self>
+    pub fn map_fatal<T, G>(self, f: G) -> Result<T, Self>
     where
-        F: FnOnce(E) -> Result<T, Self>,
+        G: FnOnce(F) -> Result<T, Self>,
     {
         match self {
             x @ FatalError::Error(_) => Err(x),
             FatalError::Fatal(x) => f(x),
         }
     }
+}
+
+impl<E> FatalError<E> {
+    /// transforms the error into it's inner type
+    pub fn into_inner(self) -> E {
+        match self {
+            FatalError::Error(x) => x,
+            FatalError::Fatal(x) => x,
+        }
+    }
+
+    /// applies f to the inner error preserving the [`FatalError::Error`] or [`FatalError::Fatal`] state
+    pub fn map<E2, F>(self, f: F) -> FatalError<E2>
+    where
+        F: FnOnce(E) -> E2,
+    {
+        match self {
+            FatalError::Error(x) => FatalError::Error(f(x)),
+            FatalError::Fatal(x) => FatalError::Fatal(f(x)),
+        }
+    }
 
     /// recover from either an error or a fatal error with the given closure
     pub fn then<T, F>(self, f: F) -> Result<T, Self>
@@ -99,19 +305,62 @@ impl<E> FatalError<E> {
     }
 }
 
-impl<E: std::fmt::Display> std::fmt::Display for FatalError<E> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<E: core::fmt::Display, F: core::fmt::Display> core::fmt::Display
+    for FatalError<E, F>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            FatalError::Error(x) => write!(f, "Error: {x}"),
-            FatalError::Fatal(x) => write!(f, "Fatal Error: {x}"),
+            FatalError::Error(x) => {
+                write!(f, "Error: ")?;
+                core::fmt::Display::fmt(x, f)
+            }
+            FatalError::Fatal(x) => {
+                write!(f, "Fatal Error: ")?;
+                core::fmt::Display::fmt(x, f)
+            }
         }
     }
 }
 
-impl<E: StdError + 'static> StdError for FatalError<E> {
+impl<E: StdError + 'static, F: StdError + 'static> FatalError<E, F> {
+    /// iterates over the inner error and its sources
+    pub fn chain(&self) -> impl Iterator<Item = &(dyn StdError + 'static)> {
+        let inner: &(dyn StdError + 'static) = match self {
+            FatalError::Error(x) => x,
+            FatalError::Fatal(x) => x,
+        };
+        core::iter::successors(Some(inner), |&x| x.source())
+    }
+}
+
+impl<E: StdError + 'static, F: StdError + 'static> StdError for FatalError<E, F> {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match self {
-            FatalError::Error(x) | FatalError::Fatal(x) => x.source(),
+            FatalError::Error(x) => Some(x),
+            FatalError::Fatal(x) => Some(x),
         }
     }
+
+    // `description` is the only method of `Error` that can tell a `FatalError` layer apart
+    // without knowing its concrete type, see `find_fatality`
+    #[allow(deprecated)]
+    fn description(&self) -> &str { self.severity().sentinel() }
+}
+
+/// walks the error and its sources, return whether the outermost [`FatalError`] layer found
+/// is fatal or `None` if there is none
+///
+/// `ErrorSet` counts as a [`FatalError`] layer.
+pub fn find_fatality(error: &(dyn StdError + 'static)) -> Option<bool> {
+    core::iter::successors(Some(error), |&x| x.source()).find_map(|x| {
+        #[allow(deprecated)]
+        let description = x.description();
+        if description == Severity::Error.sentinel() {
+            Some(false)
+        } else if description == Severity::Fatal.sentinel() {
+            Some(true)
+        } else {
+            None
+        }
+    })
 }
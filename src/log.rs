@@ -0,0 +1,154 @@
+//! [`log`] records reporting the severity of errors
+//!
+//! By default non fatal errors are logged at the `Warn` level and fatal errors at the `Error`
+//! level, [`FatalityLogger`] allows to change the levels and to rate limit non fatal errors.
+use crate::{
+    clock::{Clock, SystemClock},
+    FatalError,
+};
+use ::log::Level;
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+impl<E: std::fmt::Display, F: std::fmt::Display> FatalError<E, F> {
+    /// logs this error to the given target at the default level of its severity
+    pub fn log_fatality(&self, target: &str) {
+        match self {
+            FatalError::Error(x) => ::log::warn!(target: target, "non fatal error: {x}"),
+            FatalError::Fatal(x) => ::log::error!(target: target, "fatal error: {x}"),
+        }
+    }
+}
+
+/// Logs errors at configurable levels per severity
+///
+/// With [`FatalityLogger::rate_limit`] at most one non fatal error is logged per interval, the
+/// number of non fatal errors dropped in between, whatever they are, is reported with the
+/// next one. Fatal errors are always logged.
+#[derive(Debug)]
+pub struct FatalityLogger<C = SystemClock> {
+    target:      String,
+    error_level: Level,
+    fatal_level: Level,
+    rate_limit:  Option<Duration>,
+    clock:       C,
+    state:       Mutex<RateLimit>,
+}
+
+#[derive(Debug, Default)]
+struct RateLimit {
+    last:       Option<Instant>,
+    suppressed: u64,
+}
+
+impl FatalityLogger {
+    /// creates a logger writing to the given target without rate limit
+    pub fn new(target: impl Into<String>) -> Self {
+        Self::with_clock(target, SystemClock)
+    }
+}
+
+impl<C: Clock> FatalityLogger<C> {
+    /// creates a logger using the given clock for rate limiting
+    pub fn with_clock(target: impl Into<String>, clock: C) -> Self {
+        FatalityLogger {
+            target: target.into(),
+            error_level: Level::Warn,
+            fatal_level: Level::Error,
+            rate_limit: None,
+            clock,
+            state: Mutex::new(RateLimit::default()),
+        }
+    }
+
+    /// sets the level of non fatal errors
+    pub fn error_level(mut self, level: Level) -> Self {
+        self.error_level = level;
+        self
+    }
+
+    /// sets the level of fatal errors
+    pub fn fatal_level(mut self, level: Level) -> Self {
+        self.fatal_level = level;
+        self
+    }
+
+    /// logs at most one non fatal error per interval
+    pub fn rate_limit(mut self, interval: Duration) -> Self {
+        self.rate_limit = Some(interval);
+        self
+    }
+
+    /// logs the error at the level of its severity
+    pub fn log<E: std::fmt::Display, F: std::fmt::Display>(
+        &self, error: &FatalError<E, F>,
+    ) {
+        match error {
+            FatalError::Error(x) => {
+                let Some(suppressed) = self.acquire() else { return };
+                if suppressed > 0 {
+                    ::log::log!(
+                        target: &self.target,
+                        self.error_level,
+                        "non fatal error: {x} ({suppressed} non fatal error(s) suppressed)"
+                    );
+                } else {
+                    ::log::log!(target: &self.target, self.error_level, "non fatal error: {x}");
+                }
+            }
+            FatalError::Fatal(x) => {
+                ::log::log!(target: &self.target, self.fatal_level, "fatal error: {x}")
+            }
+        }
+    }
+
+    /// return the number of errors suppressed since the last logged one, `None` if this one
+    /// must be suppressed too
+    fn acquire(&self) -> Option<u64> {
+        let Some(interval) = self.rate_limit else { return Some(0) };
+        if !::log::log_enabled!(target: &self.target, self.error_level) {
+            return None;
+        }
+        let mut state = self.state.lock().unwrap_or_else(|x| x.into_inner());
+        let now = self.clock.now();
+        match state.last {
+            Some(last) if now.saturating_duration_since(last) < interval => {
+                state.suppressed += 1;
+                None
+            }
+            _ => {
+                state.last = Some(now);
+                Some(std::mem::take(&mut state.suppressed))
+            }
+        }
+    }
+}
+
+/// Logs the error of a `Result<T, FatalError<E, F>>`
+pub trait LogResultExt {
+    /// logs the error if any to the given target, see [`FatalError::log_fatality`]
+    fn log_fatality(self, target: &str) -> Self;
+
+    /// logs the error if any with the given logger, see [`FatalityLogger::log`]
+    fn inspect_logged<C: Clock>(self, logger: &FatalityLogger<C>) -> Self;
+}
+
+impl<T, E: std::fmt::Display, F: std::fmt::Display> LogResultExt
+    for Result<T, FatalError<E, F>>
+{
+    fn log_fatality(self, target: &str) -> Self {
+        if let Err(x) = &self {
+            x.log_fatality(target);
+        }
+        self
+    }
+
+    fn inspect_logged<C: Clock>(self, logger: &FatalityLogger<C>) -> Self {
+        if let Err(x) = &self {
+            logger.log(x);
+        }
+        self
+    }
+}
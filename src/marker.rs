@@ -0,0 +1,90 @@
+use crate::Severity;
+
+/// Context layer recording the severity of an `anyhow` or `eyre` report
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Marker(pub(crate) Severity);
+
+impl std::fmt::Display for Marker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Severity::Error => write!(f, "non fatal error"),
+            Severity::Fatal => write!(f, "fatal error"),
+        }
+    }
+}
+
+/// generates the interoperability items of a report type
+///
+/// `$context` is the method of the report adding a context layer
+macro_rules! report_markers {
+    ($krate:literal, $report:ident, $result:ident, $ext:ident, $context:ident) => {
+        use crate::{marker::Marker, FatalError, Severity};
+        use std::error::Error as StdError;
+
+        #[doc = concat!(
+            "Marks the error of an [`", $krate, "::", stringify!($result),
+            "`] with a severity"
+        )]
+        pub trait $ext<T> {
+            /// marks the error as fatal
+            fn fatal(self) -> $result<T>;
+
+            /// marks the error as non fatal
+            fn recoverable(self) -> $result<T>;
+        }
+
+        impl<T> $ext<T> for $result<T> {
+            fn fatal(self) -> $result<T> {
+                self.map_err(|x| mark(x, Severity::Fatal))
+            }
+
+            fn recoverable(self) -> $result<T> {
+                self.map_err(|x| mark(x, Severity::Error))
+            }
+        }
+
+        /// marks the error with the given severity, replacing the severity of its
+        /// outermost marker
+        pub fn mark(mut error: $report, severity: Severity) -> $report {
+            match error.downcast_mut::<Marker>() {
+                Some(x) => {
+                    x.0 = severity;
+                    error
+                }
+                None => error.$context(Marker(severity)),
+            }
+        }
+
+        /// return the severity of the outermost marker of the error, or of the outermost
+        /// [`FatalError`] of its chain if it is not marked
+        pub fn severity(error: &$report) -> Option<Severity> {
+            match error.downcast_ref::<Marker>() {
+                Some(x) => Some(x.0),
+                None => crate::find_fatality(&**error)
+                    .map(|x| if x { Severity::Fatal } else { Severity::Error }),
+            }
+        }
+
+        /// return true if the error is marked as fatal
+        pub fn is_fatal(error: &$report) -> bool {
+            severity(error) == Some(Severity::Fatal)
+        }
+
+        #[doc = concat!(
+            "converts the inner error into an [`", $krate, "::", stringify!($report),
+            "`] marked with its severity"
+        )]
+        pub fn from_fatal_error<E, F>(error: FatalError<E, F>) -> $report
+        where
+            E: StdError + Send + Sync + 'static,
+            F: StdError + Send + Sync + 'static,
+        {
+            match error {
+                FatalError::Error(x) => mark($report::new(x), Severity::Error),
+                FatalError::Fatal(x) => mark($report::new(x), Severity::Fatal),
+            }
+        }
+    };
+}
+
+pub(crate) use report_markers;
@@ -0,0 +1,306 @@
+use crate::{FatalError, Severity};
+
+/// Result of an operation that can fail with a non fatal or a fatal error
+///
+/// Equivalent to `Result<T, FatalError<E, F>>` with a flat representation, use
+/// [`try_fatal!`](crate::try_fatal) and [`try_recover!`](crate::try_recover) to propagate
+/// fatal errors.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Outcome<T, E, F = E> {
+    /// Success
+    Ok(T),
+    /// Error
+    Error(E),
+    /// Fatal error
+    Fatal(F),
+}
+
+impl<T, E, F> Outcome<T, E, F> {
+    /// return true if the outcome is a success
+    pub fn is_ok(&self) -> bool { matches!(self, Outcome::Ok(_)) }
+
+    /// return true if the outcome is a non fatal error
+    pub fn is_error(&self) -> bool { matches!(self, Outcome::Error(_)) }
+
+    /// return true if the outcome is a fatal error
+    pub fn is_fatal(&self) -> bool { matches!(self, Outcome::Fatal(_)) }
+
+    /// return the severity of the error, `None` on success
+    pub fn severity(&self) -> Option<Severity> {
+        match self {
+            Outcome::Ok(_) => None,
+            Outcome::Error(_) => Some(Severity::Error),
+            Outcome::Fatal(_) => Some(Severity::Fatal),
+        }
+    }
+
+    /// return the value if any, discarding errors
+    pub fn ok(self) -> Option<T> {
+        match self {
+            Outcome::Ok(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// return the non fatal error if any
+    pub fn error(self) -> Option<E> {
+        match self {
+            Outcome::Error(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// return the fatal error if any
+    pub fn fatal(self) -> Option<F> {
+        match self {
+            Outcome::Fatal(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// converts from `&Outcome<T, E, F>` to `Outcome<&T, &E, &F>`
+    pub fn as_ref(&self) -> Outcome<&T, &E, &F> {
+        match self {
+            Outcome::Ok(x) => Outcome::Ok(x),
+            Outcome::Error(x) => Outcome::Error(x),
+            Outcome::Fatal(x) => Outcome::Fatal(x),
+        }
+    }
+
+    /// converts from `&mut Outcome<T, E, F>` to `Outcome<&mut T, &mut E, &mut F>`
+    pub fn as_mut(&mut self) -> Outcome<&mut T, &mut E, &mut F> {
+        match self {
+            Outcome::Ok(x) => Outcome::Ok(x),
+            Outcome::Error(x) => Outcome::Error(x),
+            Outcome::Fatal(x) => Outcome::Fatal(x),
+        }
+    }
+
+    /// applies f to the value leaving errors untouched
+    pub fn map<U, G>(self, f: G) -> Outcome<U, E, F>
+    where
+        G: FnOnce(T) -> U,
+    {
+        match self {
+            Outcome::Ok(x) => Outcome::Ok(f(x)),
+            Outcome::Error(x) => Outcome::Error(x),
+            Outcome::Fatal(x) => Outcome::Fatal(x),
+        }
+    }
+
+    /// applies f to a non fatal error
+    pub fn map_err<E2, G>(self, f: G) -> Outcome<T, E2, F>
+    where
+        G: FnOnce(E) -> E2,
+    {
+        match self {
+            Outcome::Ok(x) => Outcome::Ok(x),
+            Outcome::Error(x) => Outcome::Error(f(x)),
+            Outcome::Fatal(x) => Outcome::Fatal(x),
+        }
+    }
+
+    /// applies f to a fatal error
+    pub fn map_fatal<F2, G>(self, f: G) -> Outcome<T, E, F2>
+    where
+        G: FnOnce(F) -> F2,
+    {
+        match self {
+            Outcome::Ok(x) => Outcome::Ok(x),
+            Outcome::Error(x) => Outcome::Error(x),
+            Outcome::Fatal(x) => Outcome::Fatal(f(x)),
+        }
+    }
+
+    /// calls f with the value on success, errors are returned as is
+    pub fn and_then<U, G>(self, f: G) -> Outcome<U, E, F>
+    where
+        G: FnOnce(T) -> Outcome<U, E, F>,
+    {
+        match self {
+            Outcome::Ok(x) => f(x),
+            Outcome::Error(x) => Outcome::Error(x),
+            Outcome::Fatal(x) => Outcome::Fatal(x),
+        }
+    }
+
+    /// calls f on a non fatal error
+    pub fn or_else_error<G>(self, f: G) -> Self
+    where
+        G: FnOnce(E) -> Self,
+    {
+        match self {
+            Outcome::Error(x) => f(x),
+            x => x,
+        }
+    }
+
+    /// calls f on a fatal error
+    pub fn or_else_fatal<G>(self, f: G) -> Self
+    where
+        G: FnOnce(F) -> Self,
+    {
+        match self {
+            Outcome::Fatal(x) => f(x),
+            x => x,
+        }
+    }
+
+    /// recover a non fatal error with the given closure, fatal errors are returned as is
+    pub fn or_recover<G>(self, f: G) -> Result<T, F>
+    where
+        G: FnOnce(E) -> T,
+    {
+        match self {
+            Outcome::Ok(x) => Ok(x),
+            Outcome::Error(x) => Ok(f(x)),
+            Outcome::Fatal(x) => Err(x),
+        }
+    }
+
+    /// discard non fatal errors
+    ///
+    /// return `Ok(Some(T))` on success, `Ok(None)` on a non fatal error and `Err(F)` on a fatal error
+    pub fn ok_or_fatal(self) -> Result<Option<T>, F> {
+        match self {
+            Outcome::Ok(x) => Ok(Some(x)),
+            Outcome::Error(_) => Ok(None),
+            Outcome::Fatal(x) => Err(x),
+        }
+    }
+
+    /// Makes a non fatal error fatal
+    pub fn escalate(self) -> Self
+    where
+        E: Into<F>,
+    {
+        match self {
+            Outcome::Error(x) => Outcome::Fatal(x.into()),
+            x => x,
+        }
+    }
+
+    /// Makes a fatal error non fatal
+    pub fn deescalate(self) -> Self
+    where
+        F: Into<E>,
+    {
+        match self {
+            Outcome::Fatal(x) => Outcome::Error(x.into()),
+            x => x,
+        }
+    }
+
+    /// return the value or `default` on error
+    pub fn unwrap_or(self, default: T) -> T {
+        match self {
+            Outcome::Ok(x) => x,
+            _ => default,
+        }
+    }
+
+    /// return the value or computes it from the error
+    pub fn unwrap_or_else<G>(self, f: G) -> T
+    where
+        G: FnOnce(FatalError<E, F>) -> T,
+    {
+        match self {
+            Outcome::Ok(x) => x,
+            Outcome::Error(x) => f(FatalError::Error(x)),
+            Outcome::Fatal(x) => f(FatalError::Fatal(x)),
+        }
+    }
+
+    /// converts the outcome into a `Result<T, FatalError<E, F>>`
+    pub fn into_result(self) -> Result<T, FatalError<E, F>> { self.into() }
+}
+
+impl<T, E: core::fmt::Debug, F: core::fmt::Debug> Outcome<T, E, F> {
+    /// return the value
+    ///
+    /// # Panics
+    ///
+    /// panics if the outcome is an error
+    #[track_caller]
+    pub fn unwrap(self) -> T { self.expect("called `Outcome::unwrap()` on an error") }
+
+    /// return the value
+    ///
+    /// # Panics
+    ///
+    /// panics with `msg` if the outcome is an error
+    #[track_caller]
+    pub fn expect(self, msg: &str) -> T {
+        match self {
+            Outcome::Ok(x) => x,
+            Outcome::Error(x) => panic!("{msg}: Error({x:?})"),
+            Outcome::Fatal(x) => panic!("{msg}: Fatal({x:?})"),
+        }
+    }
+}
+
+impl<T, E, F> From<Result<T, FatalError<E, F>>> for Outcome<T, E, F> {
+    fn from(value: Result<T, FatalError<E, F>>) -> Self {
+        match value {
+            Ok(x) => Outcome::Ok(x),
+            Err(FatalError::Error(x)) => Outcome::Error(x),
+            Err(FatalError::Fatal(x)) => Outcome::Fatal(x),
+        }
+    }
+}
+
+impl<T, E, F> From<Outcome<T, E, F>> for Result<T, FatalError<E, F>> {
+    fn from(value: Outcome<T, E, F>) -> Self {
+        match value {
+            Outcome::Ok(x) => Ok(x),
+            Outcome::Error(x) => Err(FatalError::Error(x)),
+            Outcome::Fatal(x) => Err(FatalError::Fatal(x)),
+        }
+    }
+}
+
+/// Builds a failed value from a fatal error, used by [`try_fatal!`](crate::try_fatal) and
+/// [`try_recover!`](crate::try_recover) to propagate fatal errors
+pub trait FromFatal<F> {
+    /// creates a value holding the fatal error
+    fn from_fatal(fatal: F) -> Self;
+}
+
+impl<T, E, F, F2: From<F>> FromFatal<F> for Result<T, FatalError<E, F2>> {
+    fn from_fatal(fatal: F) -> Self { Err(FatalError::Fatal(fatal.into())) }
+}
+
+impl<T, E, F, F2: From<F>> FromFatal<F> for Outcome<T, E, F2> {
+    fn from_fatal(fatal: F) -> Self { Outcome::Fatal(fatal.into()) }
+}
+
+/// Propagates fatal errors and evaluates to a `Result<T, E>` holding the value or the non
+/// fatal error
+///
+/// Accepts an [`Outcome`] or a `Result<T, FatalError<E, F>>`, the enclosing function must
+/// return a type implementing [`FromFatal`], the fatal error is converted with [`From`].
+#[macro_export]
+macro_rules! try_fatal {
+    ($expr:expr $(,)?) => {
+        match $crate::Outcome::from($expr) {
+            $crate::Outcome::Ok(x) => ::core::result::Result::Ok(x),
+            $crate::Outcome::Error(x) => ::core::result::Result::Err(x),
+            $crate::Outcome::Fatal(x) => return $crate::FromFatal::from_fatal(x),
+        }
+    };
+}
+
+/// Propagates fatal errors and hands non fatal errors to a local handler
+///
+/// Evaluates to the value on success or to the result of `$handler` called with the non
+/// fatal error, see [`try_fatal!`](crate::try_fatal) for the propagation of fatal errors.
+#[macro_export]
+macro_rules! try_recover {
+    ($expr:expr, $handler:expr $(,)?) => {
+        match $crate::Outcome::from($expr) {
+            $crate::Outcome::Ok(x) => x,
+            $crate::Outcome::Error(x) => ($handler)(x),
+            $crate::Outcome::Fatal(x) => return $crate::FromFatal::from_fatal(x),
+        }
+    };
+}
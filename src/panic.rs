@@ -0,0 +1,168 @@
+//! Conversion of panics into fatal errors
+//!
+//! [`catch`] runs a closure and turns a panic into a [`FatalError::Fatal`] holding a
+//! [`PanicError`]. The first call installs a panic hook recording the location and the
+//! backtrace of panics raised inside [`catch`], every panic is then forwarded to the previous
+//! hook, so caught panics are still reported as with [`std::panic::catch_unwind`]. Installing
+//! another hook afterwards disables the recording of the location.
+use crate::FatalError;
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    cell::{Cell, RefCell},
+    error::Error as StdError,
+    panic::{self, UnwindSafe},
+    sync::Once,
+};
+
+/// Panic caught by [`catch`]
+#[derive(Debug)]
+pub struct PanicError {
+    message:   String,
+    location:  Option<String>,
+    backtrace: Backtrace,
+}
+
+impl PanicError {
+    /// return the message of the panic, or a placeholder if the payload is not a string
+    pub fn message(&self) -> &str { &self.message }
+
+    /// return the location of the panic as `file:line:column` if it was recorded
+    pub fn location(&self) -> Option<&str> { self.location.as_deref() }
+
+    /// return the backtrace of the panic, captured only with the `backtrace` feature
+    pub fn backtrace(&self) -> &Backtrace { &self.backtrace }
+
+    fn new(payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(x) => *x,
+            Err(x) => match x.downcast::<&'static str>() {
+                Ok(x) => x.to_string(),
+                Err(_) => "Box<dyn Any>".to_string(),
+            },
+        };
+        // the recorded panic may have been caught before reaching `catch`, and panics resumed
+        // with `resume_unwind` do not run the hook
+        let (location, backtrace) = match CAPTURED.take() {
+            Some(x) if x.message.as_deref().is_none_or(|x| x == message) => {
+                (x.location, x.backtrace)
+            }
+            _ => (None, Backtrace::disabled()),
+        };
+        PanicError { message, location, backtrace }
+    }
+}
+
+impl std::fmt::Display for PanicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(x) => write!(f, "panicked at {x}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+impl StdError for PanicError {}
+
+struct Captured {
+    message:   Option<String>,
+    location:  Option<String>,
+    backtrace: Backtrace,
+}
+
+thread_local! {
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    static CAPTURED: RefCell<Option<Captured>> = const { RefCell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+fn install_hook() {
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() > 0 {
+                let payload = info.payload();
+                let message = payload
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| payload.downcast_ref::<&str>().map(|x| x.to_string()));
+                CAPTURED.set(Some(Captured {
+                    message,
+                    location: info.location().map(|x| x.to_string()),
+                    backtrace: crate::backtrace::capture(),
+                }));
+            }
+            previous(info);
+        }));
+    });
+}
+
+/// Marks the current thread as catching panics until dropped
+///
+/// The record of an outer panic, for example when `catch` is called by a destructor while
+/// unwinding, is set aside and restored on drop. Panics caught by a `catch_unwind` inside
+/// `catch` leave their record behind, which is dropped too.
+struct Scope(Option<Captured>);
+
+impl Scope {
+    fn enter() -> Self {
+        CATCHING.set(CATCHING.get() + 1);
+        Scope(CAPTURED.take())
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        CATCHING.set(CATCHING.get() - 1);
+        CAPTURED.set(self.0.take());
+    }
+}
+
+/// calls f and converts a panic into a [`FatalError::Fatal`]
+pub fn catch<T, G>(f: G) -> Result<T, FatalError<PanicError>>
+where
+    G: FnOnce() -> T + UnwindSafe,
+{
+    install_hook();
+    let _scope = Scope::enter();
+    panic::catch_unwind(f).map_err(|x| FatalError::Fatal(PanicError::new(x)))
+}
+
+/// wraps the future to convert a panic raised while polling it into a [`FatalError::Fatal`]
+#[cfg(feature = "futures")]
+pub fn catch_future<Fut>(future: Fut) -> CatchUnwind<Fut>
+where
+    Fut: std::future::Future + UnwindSafe,
+{
+    CatchUnwind { future }
+}
+
+#[cfg(feature = "futures")]
+pin_project_lite::pin_project! {
+    /// Future returned by [`catch_future`]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct CatchUnwind<Fut> {
+        #[pin]
+        future: Fut,
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<Fut> std::future::Future for CatchUnwind<Fut>
+where
+    Fut: std::future::Future + UnwindSafe,
+{
+    type Output = Result<Fut::Output, FatalError<PanicError>>;
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        let future = self.project().future;
+        match catch(panic::AssertUnwindSafe(|| future.poll(cx))) {
+            Ok(std::task::Poll::Ready(x)) => std::task::Poll::Ready(Ok(x)),
+            Ok(std::task::Poll::Pending) => std::task::Poll::Pending,
+            Err(x) => std::task::Poll::Ready(Err(x)),
+        }
+    }
+}
@@ -0,0 +1,286 @@
+//! Escalation policy loaded from configuration
+//!
+//! A [`Policy`] is a list of rules, the first rule whose [`Matcher`] matches an error decides
+//! its new severity. Policies can be loaded with any serde format, in TOML:
+//!
+//! ```toml
+//! [[rules]]
+//! match = { io_kind = "ConnectionRefused" }
+//! action = "deescalate"
+//!
+//! [[rules]]
+//! match = { type_name = "ParseIntError", regex = "^invalid digit" }
+//! action = "escalate"
+//! ```
+use crate::{FatalError, Severity};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{error::Error as StdError, io};
+
+/// Decision taken by a [`Policy`] for an error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    /// makes the error fatal
+    Escalate,
+    /// makes the error non fatal
+    Deescalate,
+    /// leaves the error as is
+    Keep,
+}
+
+/// Conditions on an error, all the conditions set must hold for the matcher to match
+///
+/// The `io_kind` and `code` conditions look for an [`io::Error`] in the chain of the error.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Matcher {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    severity:  Option<Severity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    type_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    contains:  Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "regex_serde")]
+    regex:     Option<Regex>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "io_kind_serde")]
+    io_kind:   Option<io::ErrorKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    code:      Option<i32>,
+}
+
+impl Matcher {
+    /// creates a matcher matching every error
+    pub fn new() -> Self { Self::default() }
+
+    /// matches errors of the given severity
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = Some(severity);
+        self
+    }
+
+    /// matches errors whose type name, as given by [`std::any::type_name`], is `name` or ends
+    /// with `::name`
+    ///
+    /// only the type of the error wrapped by the [`FatalError`] is checked, the types of its
+    /// sources are unknown once erased into `dyn Error`
+    pub fn type_name(mut self, name: impl Into<String>) -> Self {
+        self.type_name = Some(name.into());
+        self
+    }
+
+    /// matches errors whose [`std::fmt::Display`] contains `pattern`
+    pub fn contains(mut self, pattern: impl Into<String>) -> Self {
+        self.contains = Some(pattern.into());
+        self
+    }
+
+    /// matches errors whose [`std::fmt::Display`] matches `regex`
+    pub fn regex(mut self, regex: Regex) -> Self {
+        self.regex = Some(regex);
+        self
+    }
+
+    /// matches [`io::Error`]s of the given kind
+    ///
+    /// in configuration the kind is named as by its [`std::fmt::Debug`] implementation, for
+    /// example `"ConnectionRefused"`, unknown names are rejected
+    pub fn io_kind(mut self, kind: io::ErrorKind) -> Self {
+        self.io_kind = Some(kind);
+        self
+    }
+
+    /// matches [`io::Error`]s with the given raw os error code
+    pub fn code(mut self, code: i32) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// return true if the error matches every condition of the matcher
+    pub fn matches<E: StdError + 'static>(&self, error: &FatalError<E>) -> bool {
+        let inner: &(dyn StdError + 'static) = match error {
+            FatalError::Error(x) => x,
+            FatalError::Fatal(x) => x,
+        };
+        if self.severity.is_some_and(|x| x != error.severity()) {
+            return false;
+        }
+        if let Some(name) = &self.type_name {
+            let type_name = std::any::type_name::<E>();
+            if type_name != name && !type_name.ends_with(&format!("::{name}")) {
+                return false;
+            }
+        }
+        if self.contains.is_some() || self.regex.is_some() {
+            let display = inner.to_string();
+            if self.contains.as_ref().is_some_and(|x| !display.contains(x.as_str())) {
+                return false;
+            }
+            if self.regex.as_ref().is_some_and(|x| !x.is_match(&display)) {
+                return false;
+            }
+        }
+        if self.io_kind.is_some() || self.code.is_some() {
+            let io = std::iter::successors(Some(inner), |&x| x.source())
+                .find_map(|x| x.downcast_ref::<io::Error>());
+            let Some(io) = io else { return false };
+            if self.io_kind.is_some_and(|x| x != io.kind()) {
+                return false;
+            }
+            if self.code.is_some_and(|x| io.raw_os_error() != Some(x)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Rule of a [`Policy`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    #[serde(rename = "match")]
+    matcher: Matcher,
+    action:  Action,
+}
+
+impl Rule {
+    /// creates a rule applying `action` to the errors matched by `matcher`
+    pub fn new(matcher: Matcher, action: Action) -> Self { Rule { matcher, action } }
+
+    /// return the matcher of the rule
+    pub fn matcher(&self) -> &Matcher { &self.matcher }
+
+    /// return the action of the rule
+    pub fn action(&self) -> Action { self.action }
+}
+
+/// Ordered list of rules deciding the severity of errors, see the
+/// [module documentation](self)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+impl Policy {
+    /// creates an empty policy keeping every error as is
+    pub fn new() -> Self { Self::default() }
+
+    /// appends a rule, rules are tried in the order they were added
+    pub fn rule(mut self, matcher: Matcher, action: Action) -> Self {
+        self.rules.push(Rule::new(matcher, action));
+        self
+    }
+
+    /// return the rules of the policy
+    pub fn rules(&self) -> &[Rule] { &self.rules }
+
+    /// return the action of the first rule matching the error, [`Action::Keep`] if none does
+    pub fn decide<E: StdError + 'static>(&self, error: &FatalError<E>) -> Action {
+        self.rules
+            .iter()
+            .find(|x| x.matcher.matches(error))
+            .map_or(Action::Keep, |x| x.action)
+    }
+
+    /// applies the action decided for the error
+    pub fn apply<E: StdError + 'static>(&self, error: FatalError<E>) -> FatalError<E> {
+        match self.decide(&error) {
+            Action::Escalate => error.escalate(),
+            Action::Deescalate => error.deescalate(),
+            Action::Keep => error,
+        }
+    }
+}
+
+mod regex_serde {
+    use regex::Regex;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        regex: &Option<Regex>, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match regex {
+            Some(x) => serializer.serialize_some(x.as_str()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Regex>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|x| Regex::new(&x).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+mod io_kind_serde {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::io::ErrorKind;
+
+    /// kinds that can be named in configuration
+    const KINDS: &[ErrorKind] = &[
+        ErrorKind::NotFound,
+        ErrorKind::PermissionDenied,
+        ErrorKind::ConnectionRefused,
+        ErrorKind::ConnectionReset,
+        ErrorKind::HostUnreachable,
+        ErrorKind::NetworkUnreachable,
+        ErrorKind::ConnectionAborted,
+        ErrorKind::NotConnected,
+        ErrorKind::AddrInUse,
+        ErrorKind::AddrNotAvailable,
+        ErrorKind::NetworkDown,
+        ErrorKind::BrokenPipe,
+        ErrorKind::AlreadyExists,
+        ErrorKind::WouldBlock,
+        ErrorKind::NotADirectory,
+        ErrorKind::IsADirectory,
+        ErrorKind::DirectoryNotEmpty,
+        ErrorKind::ReadOnlyFilesystem,
+        ErrorKind::StaleNetworkFileHandle,
+        ErrorKind::InvalidInput,
+        ErrorKind::InvalidData,
+        ErrorKind::TimedOut,
+        ErrorKind::WriteZero,
+        ErrorKind::StorageFull,
+        ErrorKind::NotSeekable,
+        ErrorKind::QuotaExceeded,
+        ErrorKind::FileTooLarge,
+        ErrorKind::ResourceBusy,
+        ErrorKind::ExecutableFileBusy,
+        ErrorKind::Deadlock,
+        ErrorKind::CrossesDevices,
+        ErrorKind::TooManyLinks,
+        ErrorKind::ArgumentListTooLong,
+        ErrorKind::Interrupted,
+        ErrorKind::Unsupported,
+        ErrorKind::UnexpectedEof,
+        ErrorKind::OutOfMemory,
+        ErrorKind::Other,
+    ];
+
+    pub fn serialize<S: Serializer>(
+        kind: &Option<ErrorKind>, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match kind {
+            Some(x) => serializer.serialize_some(&format!("{x:?}")),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<ErrorKind>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|x| {
+                KINDS.iter().find(|kind| format!("{kind:?}") == x).copied().ok_or_else(
+                    || D::Error::custom(format!("unknown io error kind `{x}`")),
+                )
+            })
+            .transpose()
+    }
+}
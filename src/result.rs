@@ -0,0 +1,193 @@
+use crate::{ContextError, FatalError};
+
+/// Fatality aware combinators for `Result<T, FatalError<E, F>>`
+pub trait FatalResultExt<T, E, F = E> {
+    /// return true if the result is a non fatal error
+    fn is_recoverable_err(&self) -> bool;
+
+    /// return true if the result is a fatal error
+    fn is_fatal_err(&self) -> bool;
+
+    /// return the non fatal error if any, discarding the value and fatal errors
+    fn recoverable_err(self) -> Option<E>;
+
+    /// return the fatal error if any, discarding the value and non fatal errors
+    fn fatal_err(self) -> Option<F>;
+
+    /// makes the error fatal, see [`FatalError::escalate`]
+    fn escalate_err(self) -> Self
+    where
+        E: Into<F>;
+
+    /// makes the error non fatal, see [`FatalError::deescalate`]
+    fn deescalate_err(self) -> Self
+    where
+        F: Into<E>;
+
+    /// applies f to the inner error preserving the [`FatalError::Error`] or [`FatalError::Fatal`] state
+    ///
+    /// a fatal error is converted into `E` before applying f
+    fn map_inner_err<E2, G>(self, f: G) -> Result<T, FatalError<E2>>
+    where
+        F: Into<E>,
+        G: FnOnce(E) -> E2;
+
+    /// drops the fatality of the error, a fatal error is converted into `E`
+    fn into_inner_err(self) -> Result<T, E>
+    where
+        F: Into<E>;
+
+    /// recover a non fatal error with the given closure, fatal errors are returned as is
+    fn or_recover<G>(self, f: G) -> Result<T, F>
+    where
+        G: FnOnce(E) -> T;
+
+    /// calls f on a non fatal error, see [`FatalError::map_error`]
+    fn or_else_error<G>(self, f: G) -> Self
+    where
+        G: FnOnce(E) -> Self;
+
+    /// calls f on a fatal error, see [`FatalError::map_fatal`]
+    fn or_else_fatal<G>(self, f: G) -> Self
+    where
+        G: FnOnce(F) -> Self;
+
+    /// discard non fatal errors
+    ///
+    /// return `Ok(Some(T))` on success, `Ok(None)` on a non fatal error and `Err(F)` on a fatal error
+    fn ok_or_fatal(self) -> Result<Option<T>, F>;
+
+    /// wraps the error in a [`Context`](crate::Context) layer, see [`FatalError::context`]
+    fn context<C>(self, context: C) -> Result<T, ContextError<C, E, F>>;
+
+    /// wraps the error in a [`Context`](crate::Context) layer built lazily by f, see [`FatalError::context`]
+    fn with_context<C, G>(self, f: G) -> Result<T, ContextError<C, E, F>>
+    where
+        G: FnOnce() -> C;
+}
+
+impl<T, E, F> FatalResultExt<T, E, F> for Result<T, FatalError<E, F>> {
+    fn is_recoverable_err(&self) -> bool { matches!(self, Err(FatalError::Error(_))) }
+
+    fn is_fatal_err(&self) -> bool { matches!(self, Err(FatalError::Fatal(_))) }
+
+    fn recoverable_err(self) -> Option<E> {
+        match self {
+            Err(FatalError::Error(x)) => Some(x),
+            _ => None,
+        }
+    }
+
+    fn fatal_err(self) -> Option<F> {
+        match self {
+            Err(FatalError::Fatal(x)) => Some(x),
+            _ => None,
+        }
+    }
+
+    fn escalate_err(self) -> Self
+    where
+        E: Into<F>,
+    {
+        self.map_err(FatalError::escalate)
+    }
+
+    fn deescalate_err(self) -> Self
+    where
+        F: Into<E>,
+    {
+        self.map_err(FatalError::deescalate)
+    }
+
+    fn map_inner_err<E2, G>(self, f: G) -> Result<T, FatalError<E2>>
+    where
+        F: Into<E>,
+        G: FnOnce(E) -> E2,
+    {
+        self.map_err(|x| match x {
+            FatalError::Error(x) => FatalError::Error(f(x)),
+            FatalError::Fatal(x) => FatalError::Fatal(f(x.into())),
+        })
+    }
+
+    fn into_inner_err(self) -> Result<T, E>
+    where
+        F: Into<E>,
+    {
+        self.map_err(|x| match x {
+            FatalError::Error(x) => x,
+            FatalError::Fatal(x) => x.into(),
+        })
+    }
+
+    fn or_recover<G>(self, f: G) -> Result<T, F>
+    where
+        G: FnOnce(E) -> T,
+    {
+        match self {
+            Ok(x) => Ok(x),
+            Err(FatalError::Error(x)) => Ok(f(x)),
+            Err(FatalError::Fatal(x)) => Err(x),
+        }
+    }
+
+    fn or_else_error<G>(self, f: G) -> Self
+    where
+        G: FnOnce(E) -> Self,
+    {
+        self.or_else(|x| x.map_error(f))
+    }
+
+    fn or_else_fatal<G>(self, f: G) -> Self
+    where
+        G: FnOnce(F) -> Self,
+    {
+        self.or_else(|x| x.map_fatal(f))
+    }
+
+    fn ok_or_fatal(self) -> Result<Option<T>, F> {
+        match self {
+            Ok(x) => Ok(Some(x)),
+            Err(FatalError::Error(_)) => Ok(None),
+            Err(FatalError::Fatal(x)) => Err(x),
+        }
+    }
+
+    fn context<C>(self, context: C) -> Result<T, ContextError<C, E, F>> {
+        self.map_err(|x| x.context(context))
+    }
+
+    fn with_context<C, G>(self, f: G) -> Result<T, ContextError<C, E, F>>
+    where
+        G: FnOnce() -> C,
+    {
+        self.map_err(|x| x.context(f()))
+    }
+}
+
+/// Classify the error of a plain `Result<T, E>`
+pub trait IntoFatalResultExt<T, E> {
+    /// wraps the error into a [`FatalError::Error`]
+    fn into_error(self) -> Result<T, FatalError<E>>;
+
+    /// wraps the error into a [`FatalError::Fatal`]
+    fn into_fatal(self) -> Result<T, FatalError<E>>;
+
+    /// wraps the error into a [`FatalError::Fatal`] if f returns true, otherwise into a [`FatalError::Error`]
+    fn into_fatal_if<F>(self, f: F) -> Result<T, FatalError<E>>
+    where
+        F: FnOnce(&E) -> bool;
+}
+
+impl<T, E> IntoFatalResultExt<T, E> for Result<T, E> {
+    fn into_error(self) -> Result<T, FatalError<E>> { self.map_err(FatalError::Error) }
+
+    fn into_fatal(self) -> Result<T, FatalError<E>> { self.map_err(FatalError::Fatal) }
+
+    fn into_fatal_if<F>(self, f: F) -> Result<T, FatalError<E>>
+    where
+        F: FnOnce(&E) -> bool,
+    {
+        self.map_err(|x| if f(&x) { FatalError::Fatal(x) } else { FatalError::Error(x) })
+    }
+}
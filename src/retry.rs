@@ -0,0 +1,271 @@
+//! Retry an operation as long as it fails with non fatal errors
+use crate::{
+    clock::{Clock, SystemClock},
+    FatalError,
+};
+use std::{
+    collections::hash_map::RandomState,
+    error::Error as StdError,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+/// Delay to wait between two attempts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// wait the same duration after every attempt
+    Fixed(Duration),
+    /// wait `initial * factor^(n - 1)` after the n-th attempt, capped to `max`
+    Exponential {
+        /// delay after the first attempt
+        initial: Duration,
+        /// multiplier applied after every attempt
+        factor:  u32,
+        /// upper bound of the delay
+        max:     Duration,
+    },
+    /// wait a random duration between zero and the [`Backoff::Exponential`] delay
+    Jittered {
+        /// delay after the first attempt
+        initial: Duration,
+        /// multiplier applied after every attempt
+        factor:  u32,
+        /// upper bound of the delay
+        max:     Duration,
+    },
+}
+
+impl Backoff {
+    /// return the longest delay to wait after the given attempt, attempts start at 1
+    pub fn max_delay(&self, attempt: u32) -> Duration {
+        match *self {
+            Backoff::Fixed(x) => x,
+            Backoff::Exponential { initial, factor, max }
+            | Backoff::Jittered { initial, factor, max } => initial
+                .saturating_mul(factor.saturating_pow(attempt.saturating_sub(1)))
+                .min(max),
+        }
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff::Exponential {
+            initial: Duration::from_millis(100),
+            factor:  2,
+            max:     Duration::from_secs(10),
+        }
+    }
+}
+
+/// Retry configuration
+///
+/// By default an operation is attempted 3 times with a [`Backoff::default`] delay and no
+/// deadline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: Option<u32>,
+    backoff:      Backoff,
+    deadline:     Option<Duration>,
+    seed:         Option<u64>,
+}
+
+impl RetryPolicy {
+    /// creates a policy with the default configuration
+    pub fn new() -> Self { Self::default() }
+
+    /// sets the maximum number of attempts, at least one attempt is always made
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = Some(attempts.max(1));
+        self
+    }
+
+    /// retry until the operation succeeds, fails with a fatal error or the deadline is reached
+    pub fn unlimited_attempts(mut self) -> Self {
+        self.max_attempts = None;
+        self
+    }
+
+    /// sets the delay between attempts
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// stop retrying when the next attempt would start after `deadline` since the first one
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// seeds the random generator used by [`Backoff::Jittered`]
+    pub fn jitter_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// runs `f` until it succeeds, fails with a [`FatalError::Fatal`] or the policy gives up
+    pub fn retry<T, E, F, G>(&self, f: G) -> Result<T, RetryError<E, F>>
+    where
+        G: FnMut() -> Result<T, FatalError<E, F>>,
+    {
+        self.retry_with_clock(&SystemClock, f)
+    }
+
+    /// same as [`RetryPolicy::retry`] using the given clock to measure time and wait
+    pub fn retry_with_clock<T, E, F, G, C>(
+        &self, clock: &C, mut f: G,
+    ) -> Result<T, RetryError<E, F>>
+    where
+        G: FnMut() -> Result<T, FatalError<E, F>>,
+        C: Clock + ?Sized,
+    {
+        let start = clock.now();
+        let mut schedule = self.schedule();
+        let mut errors = Vec::new();
+        loop {
+            let error = match f() {
+                Ok(x) => return Ok(x),
+                Err(x) => x,
+            };
+            let fatal = error.is_fatal();
+            errors.push(error);
+            if fatal {
+                return Err(RetryError { errors, reason: RetryStop::Fatal });
+            }
+            match schedule.next_delay(clock.now().saturating_duration_since(start)) {
+                Ok(delay) => clock.sleep(delay),
+                Err(reason) => return Err(RetryError { errors, reason }),
+            }
+        }
+    }
+
+    pub(crate) fn schedule(&self) -> Schedule<'_> {
+        let seed =
+            self.seed.unwrap_or_else(|| RandomState::new().build_hasher().finish());
+        // xorshift requires a non zero state
+        Schedule { policy: self, attempt: 0, state: seed | 1 }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: Some(3),
+            backoff:      Backoff::default(),
+            deadline:     None,
+            seed:         None,
+        }
+    }
+}
+
+/// Computes the delays of a single run of a [`RetryPolicy`]
+pub(crate) struct Schedule<'a> {
+    policy:  &'a RetryPolicy,
+    attempt: u32,
+    state:   u64,
+}
+
+impl Schedule<'_> {
+    /// return the delay to wait after a failed attempt or the reason to stop retrying
+    pub(crate) fn next_delay(
+        &mut self, elapsed: Duration,
+    ) -> Result<Duration, RetryStop> {
+        self.attempt = self.attempt.saturating_add(1);
+        if self.policy.max_attempts.is_some_and(|x| self.attempt >= x) {
+            return Err(RetryStop::Exhausted);
+        }
+        let max = self.policy.backoff.max_delay(self.attempt);
+        let delay = match self.policy.backoff {
+            Backoff::Jittered { .. } => {
+                let nanos = u64::try_from(max.as_nanos()).unwrap_or(u64::MAX);
+                Duration::from_nanos(self.random() % nanos.saturating_add(1))
+            }
+            _ => max,
+        };
+        match self.policy.deadline {
+            Some(x) if elapsed.saturating_add(delay) > x => {
+                Err(RetryStop::DeadlineExceeded)
+            }
+            _ => Ok(delay),
+        }
+    }
+
+    fn random(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
+/// Reason why a retry loop gave up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetryStop {
+    /// the last attempt failed with a fatal error
+    Fatal,
+    /// the maximum number of attempts was reached
+    Exhausted,
+    /// the next attempt would have started after the deadline
+    DeadlineExceeded,
+    /// the retry loop was cancelled before the operation succeeded
+    Cancelled,
+}
+
+impl std::fmt::Display for RetryStop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RetryStop::Fatal => write!(f, "fatal error"),
+            RetryStop::Exhausted => write!(f, "attempts exhausted"),
+            RetryStop::DeadlineExceeded => write!(f, "deadline exceeded"),
+            RetryStop::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// Report of a failed retry loop, holds the error of every attempt in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryError<E, F = E> {
+    errors: Vec<FatalError<E, F>>,
+    reason: RetryStop,
+}
+
+impl<E, F> RetryError<E, F> {
+    /// return why the retry loop stopped
+    pub fn reason(&self) -> RetryStop { self.reason }
+
+    /// return true if the retry loop stopped because of a fatal error
+    pub fn is_fatal(&self) -> bool { self.reason == RetryStop::Fatal }
+
+    /// return the number of attempts made
+    pub fn attempts(&self) -> usize { self.errors.len() }
+
+    /// return the error of every attempt in order
+    pub fn errors(&self) -> &[FatalError<E, F>] { &self.errors }
+
+    /// return the error of the last attempt
+    pub fn last(&self) -> Option<&FatalError<E, F>> { self.errors.last() }
+
+    /// transforms the report into the error of every attempt
+    pub fn into_errors(self) -> Vec<FatalError<E, F>> { self.errors }
+
+    /// transforms the report into the error of the last attempt
+    pub fn into_last(self) -> Option<FatalError<E, F>> {
+        self.errors.into_iter().next_back()
+    }
+}
+
+impl<E, F> std::fmt::Display for RetryError<E, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} after {} attempt(s)", self.reason, self.errors.len())
+    }
+}
+
+impl<E: StdError + 'static, F: StdError + 'static> StdError for RetryError<E, F> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.errors.last().map(|x| x as &(dyn StdError + 'static))
+    }
+}
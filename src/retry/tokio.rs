@@ -0,0 +1,53 @@
+//! Asynchronous retry executor measuring time with [`tokio::time`]
+use super::{RetryError, RetryPolicy, RetryStop};
+use crate::FatalError;
+use std::future::Future;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// runs the future returned by `f` until it succeeds, fails with a [`FatalError::Fatal`] or
+/// the policy gives up
+pub async fn retry<T, E, F, G, Fut>(
+    policy: &RetryPolicy, f: G,
+) -> Result<T, RetryError<E, F>>
+where
+    G: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, FatalError<E, F>>>,
+{
+    retry_cancellable(policy, &CancellationToken::new(), f).await
+}
+
+/// same as [`retry`], stops with [`RetryStop::Cancelled`] as soon as `token` is cancelled
+///
+/// the attempt in progress when the token is cancelled is dropped and not reported
+pub async fn retry_cancellable<T, E, F, G, Fut>(
+    policy: &RetryPolicy, token: &CancellationToken, mut f: G,
+) -> Result<T, RetryError<E, F>>
+where
+    G: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, FatalError<E, F>>>,
+{
+    let start = Instant::now();
+    let mut schedule = policy.schedule();
+    let mut errors = Vec::new();
+    loop {
+        let error = match token.run_until_cancelled(f()).await {
+            Some(Ok(x)) => return Ok(x),
+            Some(Err(x)) => x,
+            None => return Err(RetryError { errors, reason: RetryStop::Cancelled }),
+        };
+        let fatal = error.is_fatal();
+        errors.push(error);
+        if fatal {
+            return Err(RetryError { errors, reason: RetryStop::Fatal });
+        }
+        match schedule.next_delay(start.elapsed()) {
+            Ok(delay) => {
+                if token.run_until_cancelled(tokio::time::sleep(delay)).await.is_none() {
+                    return Err(RetryError { errors, reason: RetryStop::Cancelled });
+                }
+            }
+            Err(reason) => return Err(RetryError { errors, reason }),
+        }
+    }
+}
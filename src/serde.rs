@@ -0,0 +1,61 @@
+//! Alternative serde representations of [`FatalError`]
+//!
+//! By default a [`FatalError`] is adjacently tagged:
+//!
+//! ```json
+//! {"severity": "fatal", "error": "disk full"}
+//! ```
+use crate::FatalError;
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Externally tagged representation, `{"error": ...}` or `{"fatal": ...}`
+///
+/// To be used with `#[serde(with = "fatal_error::serde::externally_tagged")]`
+pub mod externally_tagged {
+    use super::*;
+
+    #[derive(Serialize)]
+    #[serde(rename = "FatalError", rename_all = "lowercase")]
+    enum Borrowed<'a, E, F> {
+        Error(&'a E),
+        Fatal(&'a F),
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "FatalError", rename_all = "lowercase")]
+    enum Owned<E, F> {
+        Error(E),
+        Fatal(F),
+    }
+
+    /// serializes an error as `{"error": ...}` or `{"fatal": ...}`
+    pub fn serialize<S, E, F>(
+        value: &FatalError<E, F>, serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        E: Serialize,
+        F: Serialize,
+    {
+        match value {
+            FatalError::Error(x) => Borrowed::<E, F>::Error(x),
+            FatalError::Fatal(x) => Borrowed::<E, F>::Fatal(x),
+        }
+        .serialize(serializer)
+    }
+
+    /// deserializes an error from `{"error": ...}` or `{"fatal": ...}`
+    pub fn deserialize<'de, D, E, F>(
+        deserializer: D,
+    ) -> Result<FatalError<E, F>, D::Error>
+    where
+        D: Deserializer<'de>,
+        E: Deserialize<'de>,
+        F: Deserialize<'de>,
+    {
+        Ok(match Owned::deserialize(deserializer)? {
+            Owned::Error(x) => FatalError::Error(x),
+            Owned::Fatal(x) => FatalError::Fatal(x),
+        })
+    }
+}
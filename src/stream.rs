@@ -0,0 +1,197 @@
+//! [`Stream`] adapters skipping non fatal errors and stopping on fatal ones
+use crate::FatalError;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use futures_core::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+/// Fatality aware adapters for streams of `Result<T, FatalError<E, F>>`
+///
+/// Every adapter takes a hook called with each non fatal error before it is dropped.
+pub trait FatalStreamExt<T, E, F>:
+    Stream<Item = Result<T, FatalError<E, F>>> + Sized
+{
+    /// calls f on every successful value until a fatal error is encountered
+    ///
+    /// the future returned by f can fail too, its non fatal errors are handed to the hook
+    /// and its fatal error stops the stream
+    fn try_for_each_until_fatal<H, G, Fut>(
+        self, on_error: H, f: G,
+    ) -> TryForEachUntilFatal<Self, H, G, Fut>
+    where
+        H: FnMut(E),
+        G: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<(), FatalError<E, F>>>,
+    {
+        TryForEachUntilFatal { stream: self, pending: None, on_error, f }
+    }
+
+    /// drops non fatal errors, yields the values and the fatal errors
+    fn filter_recoverable<H>(self, on_error: H) -> FilterRecoverable<Self, H>
+    where
+        H: FnMut(E),
+    {
+        FilterRecoverable { stream: self, on_error }
+    }
+
+    /// yields the successful values and ends on the first fatal error, which can be
+    /// retrieved with [`TakeUntilFatal::fatal`]
+    fn take_until_fatal<H>(self, on_error: H) -> TakeUntilFatal<Self, H, F>
+    where
+        H: FnMut(E),
+    {
+        TakeUntilFatal { stream: self, on_error, fatal: None, done: false }
+    }
+}
+
+impl<S, T, E, F> FatalStreamExt<T, E, F> for S where
+    S: Stream<Item = Result<T, FatalError<E, F>>>
+{
+}
+
+pin_project! {
+    /// Future returned by [`FatalStreamExt::try_for_each_until_fatal`]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct TryForEachUntilFatal<S, H, G, Fut> {
+        #[pin]
+        stream: S,
+        #[pin]
+        pending: Option<Fut>,
+        on_error: H,
+        f: G,
+    }
+}
+
+impl<S, H, G, Fut, T, E, F> Future for TryForEachUntilFatal<S, H, G, Fut>
+where
+    S: Stream<Item = Result<T, FatalError<E, F>>>,
+    H: FnMut(E),
+    G: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<(), FatalError<E, F>>>,
+{
+    type Output = Result<(), F>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            if let Some(pending) = this.pending.as_mut().as_pin_mut() {
+                let result = ready!(pending.poll(cx));
+                this.pending.set(None);
+                match result {
+                    Ok(()) => {}
+                    Err(FatalError::Error(x)) => (this.on_error)(x),
+                    Err(FatalError::Fatal(x)) => return Poll::Ready(Err(x)),
+                }
+            }
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(x)) => this.pending.set(Some((this.f)(x))),
+                Some(Err(FatalError::Error(x))) => (this.on_error)(x),
+                Some(Err(FatalError::Fatal(x))) => return Poll::Ready(Err(x)),
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+    }
+}
+
+pin_project! {
+    /// Stream returned by [`FatalStreamExt::filter_recoverable`]
+    #[must_use = "streams do nothing unless polled"]
+    pub struct FilterRecoverable<S, H> {
+        #[pin]
+        stream: S,
+        on_error: H,
+    }
+}
+
+impl<S, H, T, E, F> Stream for FilterRecoverable<S, H>
+where
+    S: Stream<Item = Result<T, FatalError<E, F>>>,
+    H: FnMut(E),
+{
+    type Item = Result<T, F>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(x)) => return Poll::Ready(Some(Ok(x))),
+                Some(Err(FatalError::Error(x))) => (this.on_error)(x),
+                Some(Err(FatalError::Fatal(x))) => return Poll::Ready(Some(Err(x))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { (0, self.stream.size_hint().1) }
+}
+
+impl<S, H, T, E, F> FusedStream for FilterRecoverable<S, H>
+where
+    S: FusedStream<Item = Result<T, FatalError<E, F>>>,
+    H: FnMut(E),
+{
+    fn is_terminated(&self) -> bool { self.stream.is_terminated() }
+}
+
+pin_project! {
+    /// Stream returned by [`FatalStreamExt::take_until_fatal`]
+    #[must_use = "streams do nothing unless polled"]
+    pub struct TakeUntilFatal<S, H, F> {
+        #[pin]
+        stream: S,
+        on_error: H,
+        fatal: Option<F>,
+        done: bool,
+    }
+}
+
+impl<S, H, F> TakeUntilFatal<S, H, F> {
+    /// return the fatal error that ended the stream if any
+    pub fn fatal(&self) -> Option<&F> { self.fatal.as_ref() }
+
+    /// takes the fatal error that ended the stream if any
+    pub fn take_fatal(self: Pin<&mut Self>) -> Option<F> { self.project().fatal.take() }
+}
+
+impl<S, H, T, E, F> Stream for TakeUntilFatal<S, H, F>
+where
+    S: Stream<Item = Result<T, FatalError<E, F>>>,
+    H: FnMut(E),
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut this = self.project();
+        while !*this.done {
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(x)) => return Poll::Ready(Some(x)),
+                Some(Err(FatalError::Error(x))) => (this.on_error)(x),
+                Some(Err(FatalError::Fatal(x))) => {
+                    *this.fatal = Some(x);
+                    *this.done = true;
+                }
+                None => *this.done = true,
+            }
+        }
+        Poll::Ready(None)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, self.stream.size_hint().1)
+        }
+    }
+}
+
+impl<S, H, T, E, F> FusedStream for TakeUntilFatal<S, H, F>
+where
+    S: Stream<Item = Result<T, FatalError<E, F>>>,
+    H: FnMut(E),
+{
+    fn is_terminated(&self) -> bool { self.done }
+}
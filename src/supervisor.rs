@@ -0,0 +1,399 @@
+//! Supervisor restarting worker threads after non fatal errors
+//!
+//! Workers are closures run on their own thread and returning `Result<(), FatalError<E>>`:
+//!
+//! - a worker returning `Ok(())` completed and is not restarted
+//! - after a [`FatalError::Error`] the worker is restarted after a [`Backoff`] delay, along
+//!   with the siblings selected by the [`Strategy`]
+//! - after a [`FatalError::Fatal`] or a panic the worker is not restarted and the siblings
+//!   selected by the [`Strategy`] are stopped
+//!
+//! A worker that fails while it is being stopped is not restarted, its fatal errors and
+//! panics stop the siblings selected by the [`Strategy`] in turn.
+//!
+//! When more than the allowed number of restarts happen within the period the supervisor
+//! gives up and stops every worker. Workers are stopped cooperatively, they must return once
+//! their [`StopToken`] is stopped.
+use crate::{
+    clock::{Clock, SystemClock},
+    panic::{catch, PanicError},
+    retry::Backoff,
+    FatalError,
+};
+use std::{
+    collections::VecDeque,
+    panic::AssertUnwindSafe,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Siblings affected by the failure of a worker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    /// only the failed worker
+    OneForOne,
+    /// every worker
+    OneForAll,
+    /// the failed worker and the workers added after it
+    RestForOne,
+}
+
+impl Strategy {
+    fn affected(self, index: usize, len: usize) -> std::ops::Range<usize> {
+        match self {
+            Strategy::OneForOne => index..index + 1,
+            Strategy::OneForAll => 0..len,
+            Strategy::RestForOne => index..len,
+        }
+    }
+}
+
+/// Flag asking a worker to return
+#[derive(Debug, Clone, Default)]
+pub struct StopToken(Arc<AtomicBool>);
+
+impl StopToken {
+    /// return true if the worker must return
+    pub fn is_stopped(&self) -> bool { self.0.load(Ordering::Acquire) }
+
+    fn stop(&self) { self.0.store(true, Ordering::Release) }
+}
+
+/// Final state of a worker
+#[derive(Debug)]
+pub enum ChildExit<E> {
+    /// the worker returned `Ok(())`
+    Completed,
+    /// the worker was stopped because of a sibling or because the supervisor gave up
+    Stopped,
+    /// the worker failed with a fatal error
+    Fatal(E),
+    /// the worker panicked
+    Panicked(PanicError),
+    /// the worker failed with a non fatal error after the restart limit was reached
+    GaveUp(E),
+    /// the worker failed with a non fatal error while it was being stopped, or it was not
+    /// restarted because a sibling stopped at the same time failed with a fatal error
+    Failed(E),
+}
+
+/// Reason why a supervisor returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShutdownReason {
+    /// every worker completed
+    Completed,
+    /// a worker failed with a fatal error or panicked
+    Fatal,
+    /// too many restarts happened within the period
+    RestartIntensity,
+}
+
+impl std::fmt::Display for ShutdownReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShutdownReason::Completed => write!(f, "completed"),
+            ShutdownReason::Fatal => write!(f, "fatal error"),
+            ShutdownReason::RestartIntensity => write!(f, "restart intensity reached"),
+        }
+    }
+}
+
+/// Report of a supervisor run, holds the final state of every worker in order
+#[derive(Debug)]
+pub struct SupervisorReport<E> {
+    reason: ShutdownReason,
+    exits:  Vec<(String, ChildExit<E>)>,
+}
+
+impl<E> SupervisorReport<E> {
+    /// return why the supervisor returned
+    pub fn reason(&self) -> ShutdownReason { self.reason }
+
+    /// return true if the supervisor returned because of a fatal error or too many restarts
+    pub fn is_fatal(&self) -> bool { self.reason != ShutdownReason::Completed }
+
+    /// return the name and final state of every worker in the order they were added
+    pub fn exits(&self) -> &[(String, ChildExit<E>)] { &self.exits }
+
+    /// return the final state of the named worker
+    pub fn exit(&self, name: &str) -> Option<&ChildExit<E>> {
+        self.exits.iter().find(|(x, _)| x == name).map(|(_, x)| x)
+    }
+
+    /// transforms the report into the name and final state of every worker
+    pub fn into_exits(self) -> Vec<(String, ChildExit<E>)> { self.exits }
+}
+
+type Worker<E> = Arc<dyn Fn(&StopToken) -> Result<(), FatalError<E>> + Send + Sync>;
+
+type Outcome<E> = Result<Result<(), FatalError<E>>, FatalError<PanicError>>;
+
+/// interval at which a worker waiting to restart checks its [`StopToken`]
+const STOP_POLL: Duration = Duration::from_millis(10);
+
+struct Child<E> {
+    name:     String,
+    worker:   Worker<E>,
+    token:    StopToken,
+    running:  bool,
+    restarts: VecDeque<Instant>,
+    exit:     Option<ChildExit<E>>,
+}
+
+/// Supervisor of worker threads, see the [module documentation](self)
+///
+/// By default at most 3 restarts are allowed within 5 seconds and restarts are delayed by
+/// [`Backoff::default`].
+pub struct Supervisor<E, C = SystemClock> {
+    strategy:     Strategy,
+    max_restarts: usize,
+    period:       Duration,
+    backoff:      Backoff,
+    clock:        C,
+    children:     Vec<Child<E>>,
+}
+
+impl<E: Send + 'static> Supervisor<E> {
+    /// creates a supervisor without workers
+    pub fn new(strategy: Strategy) -> Self { Self::with_clock(strategy, SystemClock) }
+}
+
+impl<E: Send + 'static, C: Clock + Clone + Send + 'static> Supervisor<E, C> {
+    /// creates a supervisor using the given clock to measure the restart period and wait
+    /// between restarts
+    pub fn with_clock(strategy: Strategy, clock: C) -> Self {
+        Supervisor {
+            strategy,
+            max_restarts: 3,
+            period: Duration::from_secs(5),
+            backoff: Backoff::default(),
+            clock,
+            children: Vec::new(),
+        }
+    }
+
+    /// allows at most `max_restarts` restarts within `period`
+    pub fn max_restarts(mut self, max_restarts: usize, period: Duration) -> Self {
+        self.max_restarts = max_restarts;
+        self.period = period;
+        self
+    }
+
+    /// sets the delay before restarting a worker, the attempt is the number of restarts of
+    /// the worker within the period
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// adds a worker, workers are started in the order they were added
+    pub fn child<G>(mut self, name: impl Into<String>, worker: G) -> Self
+    where
+        G: Fn(&StopToken) -> Result<(), FatalError<E>> + Send + Sync + 'static,
+    {
+        self.children.push(Child {
+            name:     name.into(),
+            worker:   Arc::new(worker),
+            token:    StopToken::default(),
+            running:  false,
+            restarts: VecDeque::new(),
+            exit:     None,
+        });
+        self
+    }
+
+    /// starts the workers and supervises them until they all returned
+    pub fn run(mut self) -> SupervisorReport<E> {
+        let (sender, receiver) = mpsc::channel();
+        let mut backlog = VecDeque::new();
+        let mut gave_up = false;
+        for i in 0..self.children.len() {
+            self.spawn(i, Duration::ZERO, &sender);
+        }
+        while self.children.iter().any(|x| x.running) {
+            let (i, outcome) = match backlog.pop_front() {
+                Some(x) => x,
+                None => receiver.recv().expect("the supervisor holds a sender"),
+            };
+            self.children[i].running = false;
+            match outcome {
+                Ok(Ok(())) => self.children[i].exit = Some(ChildExit::Completed),
+                Ok(Err(FatalError::Error(error))) => {
+                    let now = self.clock.now();
+                    let restarts = self.restarts_within_period(now);
+                    if restarts >= self.max_restarts {
+                        self.children[i].exit = Some(ChildExit::GaveUp(error));
+                        let outcomes =
+                            self.stop(0..self.children.len(), &receiver, &mut backlog);
+                        self.settle(outcomes, Vec::new(), &receiver, &mut backlog);
+                        gave_up = true;
+                        break;
+                    }
+                    // kept if the fatal error of a stopped sibling prevents the restart
+                    self.children[i].exit = Some(ChildExit::Failed(error));
+                    let affected = self.strategy.affected(i, self.children.len());
+                    let outcomes = self.stop(affected, &receiver, &mut backlog);
+                    for x in self.settle(outcomes, vec![i], &receiver, &mut backlog) {
+                        self.children[x].exit = None;
+                        if x == i {
+                            self.children[i].restarts.push_back(now);
+                            let attempt = self.children[i].restarts.len() as u32;
+                            self.spawn(i, self.backoff.max_delay(attempt), &sender);
+                        } else {
+                            self.spawn(x, Duration::ZERO, &sender);
+                        }
+                    }
+                }
+                Ok(Err(FatalError::Fatal(error))) => {
+                    self.children[i].exit = Some(ChildExit::Fatal(error));
+                    let affected = self.strategy.affected(i, self.children.len());
+                    let outcomes = self.stop(affected, &receiver, &mut backlog);
+                    self.settle(outcomes, Vec::new(), &receiver, &mut backlog);
+                }
+                Err(panic) => {
+                    self.children[i].exit = Some(ChildExit::Panicked(panic.into_inner()));
+                    let affected = self.strategy.affected(i, self.children.len());
+                    let outcomes = self.stop(affected, &receiver, &mut backlog);
+                    self.settle(outcomes, Vec::new(), &receiver, &mut backlog);
+                }
+            }
+        }
+        let exits: Vec<_> = self
+            .children
+            .into_iter()
+            .map(|x| (x.name, x.exit.unwrap_or(ChildExit::Stopped)))
+            .collect();
+        let reason = if gave_up {
+            ShutdownReason::RestartIntensity
+        } else if exits
+            .iter()
+            .any(|(_, x)| matches!(x, ChildExit::Fatal(_) | ChildExit::Panicked(_)))
+        {
+            ShutdownReason::Fatal
+        } else {
+            ShutdownReason::Completed
+        };
+        SupervisorReport { reason, exits }
+    }
+
+    /// runs the worker on a new thread after the given delay
+    fn spawn(
+        &mut self, index: usize, delay: Duration, sender: &Sender<(usize, Outcome<E>)>,
+    ) {
+        let child = &mut self.children[index];
+        child.token = StopToken::default();
+        child.running = true;
+        let worker = child.worker.clone();
+        let token = child.token.clone();
+        let clock = self.clock.clone();
+        let sender = sender.clone();
+        thread::Builder::new()
+            .name(child.name.clone())
+            .spawn(move || {
+                // the delay is waited in steps to return quickly once stopped
+                let mut remaining = delay;
+                while !remaining.is_zero() && !token.is_stopped() {
+                    let step = remaining.min(STOP_POLL);
+                    clock.sleep(step);
+                    remaining -= step;
+                }
+                let outcome = if token.is_stopped() {
+                    Ok(Ok(()))
+                } else {
+                    catch(AssertUnwindSafe(|| worker(&token)))
+                };
+                let _ = sender.send((index, outcome));
+            })
+            .expect("failed to spawn a worker thread");
+    }
+
+    /// stops the running workers in range and waits for them to return, return their
+    /// outcomes
+    fn stop(
+        &mut self, range: std::ops::Range<usize>,
+        receiver: &Receiver<(usize, Outcome<E>)>,
+        backlog: &mut VecDeque<(usize, Outcome<E>)>,
+    ) -> Vec<(usize, Outcome<E>)> {
+        let stopped: Vec<usize> = range.filter(|&x| self.children[x].running).collect();
+        for &x in &stopped {
+            self.children[x].token.stop();
+            self.children[x].exit = Some(ChildExit::Stopped);
+        }
+        let mut outcomes = Vec::new();
+        for (x, outcome) in std::mem::take(backlog) {
+            if stopped.contains(&x) {
+                self.children[x].running = false;
+                outcomes.push((x, outcome));
+            } else {
+                backlog.push_back((x, outcome));
+            }
+        }
+        while stopped.iter().any(|&x| self.children[x].running) {
+            let (x, outcome) = receiver.recv().expect("the supervisor holds a sender");
+            if stopped.contains(&x) {
+                self.children[x].running = false;
+                outcomes.push((x, outcome));
+            } else {
+                backlog.push_back((x, outcome));
+            }
+        }
+        outcomes
+    }
+
+    /// records the outcomes of stopped workers, return the workers to restart among the
+    /// candidates and the stopped workers that returned successfully
+    ///
+    /// a worker that failed is not restarted, its fatal errors and panics stop the siblings
+    /// selected by the strategy, which are not restarted either
+    fn settle(
+        &mut self, outcomes: Vec<(usize, Outcome<E>)>, mut candidates: Vec<usize>,
+        receiver: &Receiver<(usize, Outcome<E>)>,
+        backlog: &mut VecDeque<(usize, Outcome<E>)>,
+    ) -> Vec<usize> {
+        let mut outcomes = VecDeque::from(outcomes);
+        let mut halted = Vec::new();
+        while let Some((x, outcome)) = outcomes.pop_front() {
+            let exit = match outcome {
+                Ok(Ok(())) => {
+                    candidates.push(x);
+                    continue;
+                }
+                Ok(Err(FatalError::Error(error))) => {
+                    self.children[x].exit = Some(ChildExit::Failed(error));
+                    continue;
+                }
+                Ok(Err(FatalError::Fatal(error))) => ChildExit::Fatal(error),
+                Err(panic) => ChildExit::Panicked(panic.into_inner()),
+            };
+            self.children[x].exit = Some(exit);
+            let affected = self.strategy.affected(x, self.children.len());
+            halted.extend(affected.clone());
+            outcomes.extend(self.stop(affected, receiver, backlog));
+        }
+        candidates.retain(|x| !halted.contains(x));
+        candidates
+    }
+
+    /// drops the restarts older than the period and return the number of restarts left
+    fn restarts_within_period(&mut self, now: Instant) -> usize {
+        let period = self.period;
+        self.children
+            .iter_mut()
+            .map(|x| {
+                while x
+                    .restarts
+                    .front()
+                    .is_some_and(|&t| now.saturating_duration_since(t) >= period)
+                {
+                    x.restarts.pop_front();
+                }
+                x.restarts.len()
+            })
+            .sum()
+    }
+}
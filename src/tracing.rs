@@ -0,0 +1,75 @@
+//! [`tracing`] events and span fields reporting the severity of errors
+//!
+//! Non fatal errors are emitted at the `WARN` level and fatal errors at the `ERROR` level, with
+//! the fields `severity`, `error_type`, `error` and `sources`.
+use crate::FatalError;
+use std::error::Error as StdError;
+
+impl<E: StdError + 'static, F: StdError + 'static> FatalError<E, F> {
+    /// emits an event describing this error at the level matching its severity
+    pub fn trace(&self) {
+        let sources = Sources(self);
+        match self {
+            FatalError::Error(x) => ::tracing::warn!(
+                severity = %self.severity(),
+                error_type = std::any::type_name::<E>(),
+                error = %x,
+                sources = ?sources,
+                "non fatal error"
+            ),
+            FatalError::Fatal(x) => ::tracing::error!(
+                severity = %self.severity(),
+                error_type = std::any::type_name::<F>(),
+                error = %x,
+                sources = ?sources,
+                "fatal error"
+            ),
+        }
+    }
+}
+
+/// Lists the sources of an error when an event is recorded only
+struct Sources<'a, E, F>(&'a FatalError<E, F>);
+
+impl<E: StdError + 'static, F: StdError + 'static> std::fmt::Debug for Sources<'_, E, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.0.chain().skip(1).map(|x| x.to_string())).finish()
+    }
+}
+
+/// Traces the error of a `Result<T, FatalError<E, F>>`
+pub trait TracedResultExt {
+    /// emits an event describing the error if any, see [`FatalError::trace`]
+    fn inspect_traced(self) -> Self;
+}
+
+impl<T, E: StdError + 'static, F: StdError + 'static> TracedResultExt
+    for Result<T, FatalError<E, F>>
+{
+    fn inspect_traced(self) -> Self {
+        if let Err(x) = &self {
+            x.trace();
+        }
+        self
+    }
+}
+
+/// Records the outcome of an operation in a span
+pub trait FatalSpanExt {
+    /// records `ok`, `error` or `fatal` in the `status` field of the span
+    ///
+    /// the field must be declared when the span is created, for example with
+    /// `info_span!("job", status = tracing::field::Empty)`
+    fn record_fatality<T, E, F>(&self, result: &Result<T, FatalError<E, F>>) -> &Self;
+}
+
+impl FatalSpanExt for ::tracing::Span {
+    fn record_fatality<T, E, F>(&self, result: &Result<T, FatalError<E, F>>) -> &Self {
+        let status = match result {
+            Ok(_) => "ok",
+            Err(FatalError::Error(_)) => "error",
+            Err(FatalError::Fatal(_)) => "fatal",
+        };
+        self.record("status", status)
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{FatalError, Fatality, Severity};
+use alloc::{borrow::Cow, vec::Vec};
+use core::{error::Error as StdError, panic::Location};
+
+/// Error payload recording the trail of escalations and deescalations it went through
+///
+/// Use [`FatalError::escalate_because`] and [`FatalError::deescalate_because`] to record a
+/// [`Transition`], the alternate mode of [`core::fmt::Display`] (`{:#}`) prints the trail after
+/// the error.
+///
+/// A plain [`FatalError::escalate`] or [`FatalError::deescalate`] changes the severity
+/// without recording anything in the trail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tracked<E> {
+    error: E,
+    trail: Vec<Transition>,
+}
+
+/// [`FatalError`] recording its escalations and deescalations
+pub type TrackedError<E> = FatalError<Tracked<E>>;
+
+/// Change of severity recorded by a [`Tracked`] error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transition {
+    severity: Severity,
+    reason:   Cow<'static, str>,
+    location: &'static Location<'static>,
+}
+
+impl Transition {
+    /// return the severity of the error after the transition
+    pub fn severity(&self) -> Severity { self.severity }
+
+    /// return the reason given for the transition
+    pub fn reason(&self) -> &str { &self.reason }
+
+    /// return where the transition was made
+    pub fn location(&self) -> &'static Location<'static> { self.location }
+}
+
+impl core::fmt::Display for Transition {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.severity {
+            Severity::Error => {
+                write!(f, "deescalated at {}: {}", self.location, self.reason)
+            }
+            Severity::Fatal => {
+                write!(f, "escalated at {}: {}", self.location, self.reason)
+            }
+        }
+    }
+}
+
+impl<E> Tracked<E> {
+    /// wraps the error with an empty trail
+    pub fn new(error: E) -> Self { Tracked { error, trail: Vec::new() } }
+
+    /// return the inner error
+    pub fn inner(&self) -> &E { &self.error }
+
+    /// iterates over the transitions from the oldest to the most recent
+    pub fn trail(&self) -> core::slice::Iter<'_, Transition> { self.trail.iter() }
+
+    /// drops the trail and return the inner error
+    pub fn into_inner(self) -> E { self.error }
+
+    #[track_caller]
+    fn push(mut self, severity: Severity, reason: Cow<'static, str>) -> Self {
+        self.trail.push(Transition { severity, reason, location: Location::caller() });
+        self
+    }
+}
+
+impl<E> From<E> for Tracked<E> {
+    fn from(error: E) -> Self { Tracked::new(error) }
+}
+
+impl<E> FatalError<E> {
+    /// wraps the inner error into a [`Tracked`] payload with an empty trail
+    pub fn track(self) -> TrackedError<E> { self.map(Tracked::new) }
+}
+
+impl<E> FatalError<Tracked<E>> {
+    /// Makes this error fatal recording the reason and the location of the caller, does
+    /// nothing if the error is already fatal
+    #[track_caller]
+    pub fn escalate_because(self, reason: impl Into<Cow<'static, str>>) -> Self {
+        match self {
+            FatalError::Error(x) => {
+                FatalError::Fatal(x.push(Severity::Fatal, reason.into()))
+            }
+            x @ FatalError::Fatal(_) => x,
+        }
+    }
+
+    /// Makes this error non fatal recording the reason and the location of the caller, does
+    /// nothing if the error is already non fatal
+    #[track_caller]
+    pub fn deescalate_because(self, reason: impl Into<Cow<'static, str>>) -> Self {
+        match self {
+            x @ FatalError::Error(_) => x,
+            FatalError::Fatal(x) => {
+                FatalError::Error(x.push(Severity::Error, reason.into()))
+            }
+        }
+    }
+
+    /// iterates over the transitions of the error from the oldest to the most recent
+    pub fn trail(&self) -> core::slice::Iter<'_, Transition> {
+        match self {
+            FatalError::Error(x) => x.trail(),
+            FatalError::Fatal(x) => x.trail(),
+        }
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for Tracked<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.error, f)?;
+        if f.alternate() {
+            for x in &self.trail {
+                write!(f, "\n  {x}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<E: StdError> StdError for Tracked<E> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> { self.error.source() }
+}
+
+impl<E: Fatality> Fatality for Tracked<E> {
+    fn is_fatal(&self) -> bool { self.error.is_fatal() }
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "std")]
+use fatal_error::{BacktracedError, FatalError};
+
+#[test]
+fn escalate_captures_through_from() {
+    let error = BacktracedError::Error("timeout");
+    assert!(error.backtrace().is_none());
+    let error = error.escalate();
+    assert!(error.is_fatal());
+    assert!(error.backtrace().is_some());
+    match &error {
+        FatalError::Fatal(x) => assert_eq!(*x.inner(), "timeout"),
+        FatalError::Error(_) => unreachable!(),
+    }
+    let error = error.deescalate_backtraced();
+    assert!(matches!(error, FatalError::Error("timeout")));
+    assert!(error.backtrace().is_none());
+}
+
+#[test]
+#[cfg(not(feature = "backtrace"))]
+fn disabled_without_the_feature() {
+    use fatal_error::Backtraced;
+    use std::backtrace::BacktraceStatus;
+    assert_eq!(
+        Backtraced::new("timeout").backtrace().status(),
+        BacktraceStatus::Disabled
+    );
+    let error = BacktracedError::fatal("timeout");
+    assert_eq!(error.backtrace().unwrap().status(), BacktraceStatus::Disabled);
+}
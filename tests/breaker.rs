@@ -0,0 +1,152 @@
+#![cfg(feature = "std")]
+use common::FakeClock;
+use fatal_error::{
+    breaker::{BreakerError, BreakerState, CircuitBreaker},
+    FatalError,
+};
+use std::time::Duration;
+
+mod common;
+
+type Outcome = Result<u8, FatalError<&'static str, BreakerError<&'static str>>>;
+
+fn secs(x: u64) -> Duration { Duration::from_secs(x) }
+
+fn breaker(clock: &FakeClock) -> CircuitBreaker<&FakeClock> {
+    CircuitBreaker::with_clock(clock).threshold(3).window(secs(10)).cool_down(secs(5))
+}
+
+fn fail(breaker: &CircuitBreaker<&FakeClock>) -> Outcome {
+    breaker.call(|| Err(FatalError::Error("timeout")))
+}
+
+fn succeed(breaker: &CircuitBreaker<&FakeClock>) -> Outcome {
+    breaker.call(|| Ok::<_, FatalError<_>>(1))
+}
+
+#[test]
+fn opens_at_the_threshold() {
+    let clock = FakeClock::new();
+    let breaker = breaker(&clock);
+    assert_eq!(fail(&breaker), Err(FatalError::Error("timeout")));
+    assert_eq!(fail(&breaker), Err(FatalError::Error("timeout")));
+    assert_eq!(breaker.failures(), 2);
+    assert_eq!(breaker.state(), BreakerState::Closed);
+    assert_eq!(fail(&breaker), Err(FatalError::Fatal(BreakerError::Tripped("timeout"))));
+    assert_eq!(breaker.state(), BreakerState::Open);
+    assert_eq!(breaker.failures(), 0);
+}
+
+#[test]
+fn errors_leave_the_window() {
+    let clock = FakeClock::new();
+    let breaker = breaker(&clock);
+    fail(&breaker).unwrap_err();
+    clock.advance(secs(6));
+    fail(&breaker).unwrap_err();
+    clock.advance(secs(4));
+    // the first error is 10 seconds old
+    assert_eq!(breaker.failures(), 1);
+    assert!(fail(&breaker).unwrap_err().is_error());
+    assert_eq!(breaker.state(), BreakerState::Closed);
+    assert!(fail(&breaker).unwrap_err().is_fatal());
+}
+
+#[test]
+fn open_breaker_rejects_calls() {
+    let clock = FakeClock::new();
+    let breaker = breaker(&clock);
+    for _ in 0..3 {
+        let _ = fail(&breaker);
+    }
+    clock.advance(secs(2));
+    let mut calls = 0;
+    let result = breaker.call(|| {
+        calls += 1;
+        Ok::<_, FatalError<&str>>(1)
+    });
+    assert_eq!(calls, 0);
+    assert_eq!(
+        result,
+        Err(FatalError::Fatal(BreakerError::Open { retry_after: secs(3) }))
+    );
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Fatal Error: circuit breaker open, retry after 3s"
+    );
+}
+
+#[test]
+fn successful_probe_closes() {
+    let clock = FakeClock::new();
+    let breaker = breaker(&clock);
+    for _ in 0..3 {
+        let _ = fail(&breaker);
+    }
+    clock.advance(secs(5));
+    assert_eq!(succeed(&breaker), Ok(1));
+    assert_eq!(breaker.state(), BreakerState::Closed);
+    assert!(fail(&breaker).unwrap_err().is_error());
+}
+
+#[test]
+fn failed_probe_opens_again() {
+    let clock = FakeClock::new();
+    let breaker = breaker(&clock);
+    for _ in 0..3 {
+        let _ = fail(&breaker);
+    }
+    clock.advance(secs(5));
+    assert_eq!(fail(&breaker), Err(FatalError::Fatal(BreakerError::Tripped("timeout"))));
+    assert_eq!(breaker.state(), BreakerState::Open);
+    clock.advance(secs(1));
+    assert_eq!(
+        succeed(&breaker),
+        Err(FatalError::Fatal(BreakerError::Open { retry_after: secs(4) }))
+    );
+
+    clock.advance(secs(4));
+    let result = breaker.call(|| Err::<u8, _>(FatalError::<&str>::Fatal("disk full")));
+    assert_eq!(result, Err(FatalError::Fatal(BreakerError::Fatal("disk full"))));
+    assert_eq!(breaker.state(), BreakerState::Open);
+}
+
+#[test]
+fn single_probe_at_a_time() {
+    let clock = FakeClock::new();
+    let breaker = breaker(&clock);
+    for _ in 0..3 {
+        let _ = fail(&breaker);
+    }
+    clock.advance(secs(5));
+    let result = breaker.call(|| {
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        assert_eq!(
+            succeed(&breaker),
+            Err(FatalError::Fatal(BreakerError::Open { retry_after: Duration::ZERO }))
+        );
+        Ok::<_, FatalError<&str>>(2)
+    });
+    assert_eq!(result, Ok(2));
+    assert_eq!(breaker.state(), BreakerState::Closed);
+}
+
+#[test]
+fn fatal_errors_do_not_count() {
+    let clock = FakeClock::new();
+    let breaker = breaker(&clock);
+    for _ in 0..5 {
+        let result =
+            breaker.call(|| Err::<u8, _>(FatalError::<&str>::Fatal("disk full")));
+        assert_eq!(result, Err(FatalError::Fatal(BreakerError::Fatal("disk full"))));
+    }
+    assert_eq!(breaker.state(), BreakerState::Closed);
+    assert_eq!(breaker.failures(), 0);
+}
+
+#[test]
+fn tripped_leaves_the_error_to_source() {
+    let error = BreakerError::<_>::Tripped(std::io::Error::other("timeout"));
+    assert_eq!(error.to_string(), "circuit breaker tripped");
+    assert_eq!(std::error::Error::source(&error).unwrap().to_string(), "timeout");
+}
@@ -0,0 +1,33 @@
+// every test crate uses a subset of the helpers
+#![allow(dead_code)]
+use fatal_error::clock::Clock;
+use std::{
+    cell::{Cell, RefCell},
+    time::{Duration, Instant},
+};
+
+/// Clock advancing only when told to or slept on, recording every sleep
+pub struct FakeClock {
+    now:    Cell<Instant>,
+    sleeps: RefCell<Vec<Duration>>,
+}
+
+impl FakeClock {
+    pub fn new() -> Self {
+        FakeClock { now: Cell::new(Instant::now()), sleeps: RefCell::default() }
+    }
+
+    pub fn advance(&self, duration: Duration) { self.now.set(self.now.get() + duration) }
+
+    /// return the durations slept so far in order
+    pub fn sleeps(&self) -> Vec<Duration> { self.sleeps.borrow().clone() }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Instant { self.now.get() }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+        self.sleeps.borrow_mut().push(duration);
+    }
+}
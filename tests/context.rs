@@ -0,0 +1,57 @@
+use fatal_error::{find_fatality, FatalError, FatalResultExt};
+use std::{cell::Cell, error::Error as StdError, fmt};
+
+#[derive(Debug, PartialEq, Eq)]
+struct Failure(&'static str);
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.0) }
+}
+
+impl StdError for Failure {}
+
+#[test]
+fn alternate_display_renders_the_stack() {
+    let error = FatalError::<_>::Error(Failure("disk full"))
+        .context("writing the journal")
+        .context("saving");
+    assert_eq!(error.to_string(), "Error: saving");
+    assert_eq!(format!("{error:#}"), "Error: saving: writing the journal: disk full");
+    let error = error.escalate_with(|x| x);
+    assert_eq!(
+        format!("{error:#}"),
+        "Fatal Error: saving: writing the journal: disk full"
+    );
+}
+
+#[test]
+fn source_chain() {
+    let error = FatalError::<_>::Fatal(Failure("disk full"))
+        .context("writing the journal")
+        .context("saving");
+    let chain = error.chain().map(|x| x.to_string()).collect::<Vec<_>>();
+    assert_eq!(chain, ["saving", "writing the journal", "disk full"]);
+    let source = error.source().unwrap();
+    assert_eq!(source.to_string(), "saving");
+    // the context layers are not fatal on their own, the outer layer is
+    assert_eq!(find_fatality(source), None);
+    assert_eq!(find_fatality(&error), Some(true));
+    let inner = error.into_inner().into_inner().into_inner();
+    assert_eq!(inner, Failure("disk full"));
+}
+
+#[test]
+fn with_context_is_lazy() {
+    let calls = Cell::new(0);
+    let context = || {
+        calls.set(calls.get() + 1);
+        "loading"
+    };
+    let ok = Ok::<_, FatalError<Failure>>(1).with_context(context);
+    assert_eq!(ok.unwrap(), 1);
+    assert_eq!(calls.get(), 0);
+    let error =
+        Err::<(), _>(FatalError::<_>::Error(Failure("missing"))).with_context(context);
+    assert_eq!(calls.get(), 1);
+    assert_eq!(format!("{:#}", error.unwrap_err()), "Error: loading: missing");
+}
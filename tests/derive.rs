@@ -0,0 +1,96 @@
+#![cfg(all(feature = "derive", feature = "std"))]
+use fatal_error::{FatalError, Fatality};
+
+#[derive(Debug, Fatality)]
+enum Variants {
+    #[fatal]
+    Fatal,
+    #[recoverable]
+    Recoverable(#[allow(dead_code)] u8),
+    #[fatality(transparent)]
+    Io(std::io::Error),
+    #[fatality(transparent)]
+    Named { inner: FatalError<u8> },
+}
+
+#[derive(Debug, Fatality)]
+#[fatal]
+enum DefaultFatal {
+    Unclassified,
+    #[recoverable]
+    Recoverable,
+}
+
+#[derive(Debug, Fatality)]
+#[recoverable]
+enum DefaultRecoverable {
+    Unclassified,
+    #[fatal]
+    Fatal,
+}
+
+#[derive(Debug, Fatality)]
+enum Never {}
+
+#[derive(Debug, Fatality)]
+#[fatal]
+struct FatalStruct;
+
+#[derive(Debug, Fatality)]
+#[recoverable]
+struct RecoverableStruct {
+    #[allow(dead_code)]
+    code: u8,
+}
+
+#[derive(Debug, Fatality)]
+#[fatality(transparent)]
+struct Wrapper<E>(E);
+
+#[test]
+fn variant_attributes() {
+    assert!(Variants::Fatal.is_fatal());
+    assert!(!Variants::Recoverable(1).is_fatal());
+    assert!(Variants::Io(std::io::ErrorKind::PermissionDenied.into()).is_fatal());
+    assert!(!Variants::Io(std::io::ErrorKind::TimedOut.into()).is_fatal());
+    assert!(Variants::Named { inner: FatalError::Fatal(1) }.is_fatal());
+    assert!(!Variants::Named { inner: FatalError::Error(1) }.is_fatal());
+}
+
+#[test]
+fn enum_level_default() {
+    assert!(DefaultFatal::Unclassified.is_fatal());
+    assert!(!DefaultFatal::Recoverable.is_fatal());
+    assert!(!DefaultRecoverable::Unclassified.is_fatal());
+    assert!(DefaultRecoverable::Fatal.is_fatal());
+}
+
+#[test]
+fn structs() {
+    assert!(FatalStruct.is_fatal());
+    assert!(!RecoverableStruct { code: 1 }.is_fatal());
+    assert!(Wrapper(FatalStruct).is_fatal());
+    assert!(!Wrapper(RecoverableStruct { code: 1 }).is_fatal());
+}
+
+#[test]
+fn empty_enum() {
+    fn assert_fatality<T: Fatality>() {}
+    assert_fatality::<Never>();
+}
+
+#[test]
+fn conversions() {
+    assert!(matches!(
+        FatalError::from(Variants::Fatal),
+        FatalError::Fatal(Variants::Fatal)
+    ));
+    assert!(matches!(
+        Variants::Recoverable(2).into_fatal_error(),
+        FatalError::Error(Variants::Recoverable(2))
+    ));
+    assert!(Wrapper(FatalStruct).into_fatal_error().is_fatal());
+}
+
+#[test]
+fn compile_fail() { trybuild::TestCases::new().compile_fail("tests/ui/*.rs"); }
@@ -0,0 +1,46 @@
+#![cfg(feature = "std")]
+use fatal_error::{find_fatality, ErrorSet, FatalError, Fatality};
+use std::{error::Error as StdError, fmt, ops::ControlFlow};
+
+#[derive(Debug, PartialEq, Eq)]
+struct Failure(&'static str);
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.0) }
+}
+
+impl StdError for Failure {}
+
+#[test]
+fn fatal_once_a_fatal_error_is_pushed() {
+    let mut set = ErrorSet::new();
+    assert_eq!(set.push(FatalError::Error(Failure("a"))), ControlFlow::Continue(()));
+    assert_eq!(find_fatality(&set), Some(false));
+    assert_eq!(set.push(FatalError::Fatal(Failure("b"))), ControlFlow::Break(()));
+    assert_eq!(find_fatality(&set), Some(true));
+    assert_eq!(set.fatals().collect::<Vec<_>>(), [&Failure("b")]);
+}
+
+#[test]
+fn fatal_past_the_threshold() {
+    let mut set = ErrorSet::<Failure>::with_threshold(1);
+    assert!(set.push(FatalError::Error(Failure("a"))).is_continue());
+    assert!(!set.is_fatal());
+    assert!(set.push(FatalError::Error(Failure("b"))).is_break());
+    assert!(set.is_fatal());
+    assert_eq!(set.recoverable_count(), 2);
+    // the sources are not fatal, the set is
+    assert_eq!(find_fatality(set.source().unwrap()), Some(false));
+    assert_eq!(find_fatality(&set), Some(true));
+    let boxed: Box<dyn StdError> = Box::new(set);
+    assert!(boxed.is_fatal());
+}
+
+#[test]
+fn finish() {
+    assert!(ErrorSet::<Failure>::new().finish().is_ok());
+    let set: ErrorSet<Failure> = [FatalError::Error(Failure("a"))].into_iter().collect();
+    let error = set.finish().unwrap_err();
+    assert!(!error.is_fatal());
+    assert_eq!(error.into_inner().len(), 1);
+}
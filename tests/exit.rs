@@ -0,0 +1,44 @@
+#![cfg(feature = "std")]
+use std::process::{Command, Output};
+
+/// runs the `exit` example with the given argument
+fn run_example(args: &[&str]) -> Output {
+    Command::new(env!("CARGO"))
+        .args(["run", "--quiet", "--example", "exit", "--"])
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn non_fatal_error() {
+    let output = run_example(&["error"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(75), "{stderr}");
+    assert!(stderr.starts_with("Error: while loading config"), "{stderr}");
+    assert!(stderr.contains("Caused by:"), "{stderr}");
+    assert!(stderr.contains("connection refused"), "{stderr}");
+}
+
+#[test]
+fn fatal_error() {
+    let output = run_example(&["fatal"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(70), "{stderr}");
+    assert!(stderr.starts_with("Fatal Error: while loading config"), "{stderr}");
+    assert!(stderr.contains("Caused by:"), "{stderr}");
+    assert!(stderr.contains("connection refused"), "{stderr}");
+}
+
+#[test]
+fn success() {
+    let output = run_example(&[]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(output.stderr.is_empty());
+}
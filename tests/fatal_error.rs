@@ -0,0 +1,38 @@
+use fatal_error::FatalError;
+
+/// fatal payload distinct from the non fatal error
+#[derive(Debug, PartialEq, Eq)]
+struct Fatal(&'static str);
+
+type Error = FatalError<u32, Fatal>;
+
+#[test]
+fn escalate_with() {
+    let escalate = |x: Error| x.escalate_with(|_| Fatal("escalated"));
+    assert_eq!(escalate(FatalError::Error(1)), FatalError::Fatal(Fatal("escalated")));
+    assert_eq!(escalate(FatalError::Fatal(Fatal("a"))), FatalError::Fatal(Fatal("a")));
+}
+
+#[test]
+fn deescalate_with() {
+    let deescalate = |x: Error| x.deescalate_with(|x| x.0.len() as u32);
+    assert_eq!(deescalate(FatalError::Error(1)), FatalError::Error(1));
+    assert_eq!(deescalate(FatalError::Fatal(Fatal("abc"))), FatalError::Error(3));
+}
+
+#[test]
+fn map_both() {
+    let map = |x: Error| x.map_both(|x| x * 2, |x| x.0);
+    assert_eq!(map(FatalError::Error(1)), FatalError::<u32, &str>::Error(2));
+    assert_eq!(map(FatalError::Fatal(Fatal("a"))), FatalError::Fatal("a"));
+}
+
+#[test]
+fn as_ref() {
+    let error = Error::Error(1);
+    assert_eq!(error.as_ref(), FatalError::Error(&1));
+    let error = Error::Fatal(Fatal("a"));
+    assert_eq!(error.as_ref(), FatalError::Fatal(&Fatal("a")));
+    // the error is still usable
+    assert!(error.is_fatal());
+}
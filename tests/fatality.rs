@@ -0,0 +1,75 @@
+#![cfg(feature = "std")]
+use fatal_error::{FatalError, Fatality};
+use std::{
+    error::Error as StdError,
+    io::{self, ErrorKind},
+    sync::{
+        mpsc::{RecvError, RecvTimeoutError, SendError, TryRecvError, TrySendError},
+        Mutex, PoisonError, TryLockError,
+    },
+};
+
+/// return a poisoned mutex
+fn poisoned() -> Mutex<()> {
+    let mutex = Mutex::new(());
+    let _ = std::panic::catch_unwind(|| {
+        let _guard = mutex.lock().unwrap();
+        panic!("poison");
+    });
+    assert!(mutex.is_poisoned());
+    mutex
+}
+
+#[test]
+fn std_errors() {
+    let mutex = poisoned();
+    let table: Vec<(&str, Box<dyn Fatality + '_>, bool)> = vec![
+        ("RecvError", Box::new(RecvError), true),
+        ("RecvTimeoutError::Timeout", Box::new(RecvTimeoutError::Timeout), false),
+        (
+            "RecvTimeoutError::Disconnected",
+            Box::new(RecvTimeoutError::Disconnected),
+            true,
+        ),
+        ("TryRecvError::Empty", Box::new(TryRecvError::Empty), false),
+        ("TryRecvError::Disconnected", Box::new(TryRecvError::Disconnected), true),
+        ("SendError", Box::new(SendError(())), true),
+        ("TrySendError::Full", Box::new(TrySendError::Full(())), false),
+        ("TrySendError::Disconnected", Box::new(TrySendError::Disconnected(())), true),
+        ("PoisonError", Box::new(PoisonError::new(())), true),
+        ("TryLockError::WouldBlock", Box::new(TryLockError::<()>::WouldBlock), false),
+        ("TryLockError::Poisoned", Box::new(mutex.try_lock().unwrap_err()), true),
+        ("io::Error NotFound", Box::new(io::Error::from(ErrorKind::NotFound)), false),
+        ("io::Error Other", Box::new(io::Error::from(ErrorKind::Other)), true),
+        ("ParseIntError", Box::new("x".parse::<u8>().unwrap_err()), false),
+    ];
+    for (name, error, fatal) in &table {
+        assert_eq!(error.is_fatal(), *fatal, "{name}");
+    }
+}
+
+#[test]
+fn boxed_std_errors() {
+    let table: Vec<(&str, Box<dyn StdError + Send + Sync>, bool)> = vec![
+        ("io::Error NotFound", Box::new(io::Error::from(ErrorKind::NotFound)), false),
+        ("io::Error Other", Box::new(io::Error::from(ErrorKind::Other)), true),
+        ("RecvError", Box::new(RecvError), true),
+        ("RecvTimeoutError::Timeout", Box::new(RecvTimeoutError::Timeout), false),
+        (
+            "RecvTimeoutError::Disconnected",
+            Box::new(RecvTimeoutError::Disconnected),
+            true,
+        ),
+        ("TryRecvError::Empty", Box::new(TryRecvError::Empty), false),
+        ("TryRecvError::Disconnected", Box::new(TryRecvError::Disconnected), true),
+        ("FatalError::Fatal", Box::new(FatalError::<_>::Fatal(std::fmt::Error)), true),
+        ("FatalError::Error", Box::new(FatalError::<_>::Error(RecvError)), false),
+        // unknown errors are non fatal
+        ("fmt::Error", Box::new(std::fmt::Error), false),
+    ];
+    for (name, error, fatal) in &table {
+        assert_eq!(error.is_fatal(), *fatal, "{name}");
+        let error: &(dyn StdError + 'static) = &**error;
+        assert_eq!(error.is_fatal(), *fatal, "{name}");
+    }
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "std")]
+use fatal_error::{find_fatality, Context, FatalError, FatalResultExt, Fatality};
+use std::{error::Error as StdError, fmt, io};
+
+/// error hiding its source behind a concrete type
+#[derive(Debug)]
+struct Wrapper(Box<dyn StdError + Send + Sync>);
+
+impl fmt::Display for Wrapper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str("wrapper") }
+}
+
+impl StdError for Wrapper {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> { Some(&*self.0) }
+}
+
+fn fatal() -> FatalError<io::Error> { FatalError::Fatal(io::Error::other("disk full")) }
+
+fn recoverable() -> FatalError<io::Error> {
+    FatalError::Error(io::Error::other("connection reset"))
+}
+
+#[test]
+fn plain_errors_have_no_fatality() {
+    assert_eq!(find_fatality(&io::Error::other("disk full")), None);
+}
+
+#[test]
+fn through_box_dyn_error() {
+    let boxed: Box<dyn StdError> = Box::new(fatal());
+    assert_eq!(find_fatality(&*boxed), Some(true));
+    assert!(boxed.is_fatal());
+    let boxed: Box<dyn StdError + Send + Sync> = Box::new(recoverable());
+    assert_eq!(find_fatality(&*boxed), Some(false));
+    assert!(!boxed.is_fatal());
+}
+
+#[test]
+fn through_sources() {
+    let error = Wrapper(Box::new(Wrapper(Box::new(fatal()))));
+    assert_eq!(find_fatality(&error), Some(true));
+    // the outermost layer wins
+    let error =
+        Wrapper(Box::new(FatalError::<_, io::Error>::Error(Wrapper(Box::new(fatal())))));
+    assert_eq!(find_fatality(&error), Some(false));
+}
+
+#[test]
+fn through_context() {
+    let error = Context::new("while loading config", fatal());
+    assert_eq!(find_fatality(&error), Some(true));
+    let error = Err::<(), _>(recoverable()).context("while connecting").unwrap_err();
+    assert_eq!(find_fatality(&error), Some(false));
+    let boxed: Box<dyn StdError> = Box::new(error);
+    assert_eq!(find_fatality(&*boxed), Some(false));
+}
+
+#[cfg(feature = "anyhow")]
+#[test]
+fn through_anyhow() {
+    let error = anyhow::Error::new(fatal()).context("while loading config");
+    assert_eq!(find_fatality(&*error), Some(true));
+    let boxed: Box<dyn StdError + Send + Sync> = error.into();
+    assert_eq!(find_fatality(&*boxed), Some(true));
+}
+
+#[cfg(feature = "eyre")]
+#[test]
+fn through_eyre() {
+    let error = eyre::Report::new(recoverable()).wrap_err("while loading config");
+    assert_eq!(find_fatality(&*error), Some(false));
+    let boxed: Box<dyn StdError + Send + Sync> = error.into();
+    assert_eq!(find_fatality(&*boxed), Some(false));
+}
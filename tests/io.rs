@@ -0,0 +1,124 @@
+#![cfg(feature = "std")]
+use fatal_error::{
+    io::{default_severity, IoClassifier},
+    Severity,
+};
+use std::io::{Error, ErrorKind};
+
+const KINDS: &[ErrorKind] = &[
+    ErrorKind::NotFound,
+    ErrorKind::PermissionDenied,
+    ErrorKind::ConnectionRefused,
+    ErrorKind::ConnectionReset,
+    ErrorKind::HostUnreachable,
+    ErrorKind::NetworkUnreachable,
+    ErrorKind::ConnectionAborted,
+    ErrorKind::NotConnected,
+    ErrorKind::AddrInUse,
+    ErrorKind::AddrNotAvailable,
+    ErrorKind::NetworkDown,
+    ErrorKind::BrokenPipe,
+    ErrorKind::AlreadyExists,
+    ErrorKind::WouldBlock,
+    ErrorKind::NotADirectory,
+    ErrorKind::IsADirectory,
+    ErrorKind::DirectoryNotEmpty,
+    ErrorKind::ReadOnlyFilesystem,
+    ErrorKind::StaleNetworkFileHandle,
+    ErrorKind::InvalidInput,
+    ErrorKind::InvalidData,
+    ErrorKind::TimedOut,
+    ErrorKind::WriteZero,
+    ErrorKind::StorageFull,
+    ErrorKind::NotSeekable,
+    ErrorKind::QuotaExceeded,
+    ErrorKind::FileTooLarge,
+    ErrorKind::ResourceBusy,
+    ErrorKind::ExecutableFileBusy,
+    ErrorKind::Deadlock,
+    ErrorKind::CrossesDevices,
+    ErrorKind::TooManyLinks,
+    ErrorKind::ArgumentListTooLong,
+    ErrorKind::Interrupted,
+    ErrorKind::Unsupported,
+    ErrorKind::UnexpectedEof,
+    ErrorKind::OutOfMemory,
+    ErrorKind::Other,
+];
+
+// `InvalidFilename` is missing, it was stabilized after the minimum supported version and
+// falls in the fallback arm of `default_severity`
+
+/// return the rows of the table documented in `src/io.rs` as (kind name, severity)
+fn documented_table() -> Vec<(String, Severity)> {
+    include_str!("../src/io.rs")
+        .lines()
+        .filter_map(|x| x.strip_prefix("//! | [`"))
+        .map(|x| {
+            let (name, rest) = x.split_once('`').unwrap();
+            let severity =
+                match rest.trim_end_matches('|').rsplit('|').next().unwrap().trim() {
+                    "error" => Severity::Error,
+                    "fatal" => Severity::Fatal,
+                    x => panic!("unknown severity {x:?} for {name}"),
+                };
+            (name.to_string(), severity)
+        })
+        .collect()
+}
+
+#[test]
+fn default_severity_matches_the_documented_table() {
+    let table = documented_table();
+    assert!(table.iter().any(|(x, _)| x == "Other"));
+    for (name, severity) in &table {
+        // kinds newer than the minimum supported version can only be fatal
+        assert!(
+            KINDS.iter().any(|x| format!("{x:?}") == *name)
+                || *severity == Severity::Fatal,
+            "unknown kind {name}"
+        );
+    }
+    for kind in KINDS {
+        let name = format!("{kind:?}");
+        // kinds missing from the table fall in the `Other` row
+        let expected =
+            table.iter().find(|(x, _)| *x == name).map_or(Severity::Fatal, |x| x.1);
+        assert_eq!(default_severity(*kind), expected, "{name}");
+    }
+}
+
+#[test]
+fn classifier_defaults_to_the_table() {
+    let classifier = IoClassifier::new();
+    for kind in KINDS {
+        assert_eq!(classifier.kind_severity(*kind), default_severity(*kind), "{kind:?}");
+        assert_eq!(classifier.classify_kind(*kind).severity(), default_severity(*kind));
+    }
+}
+
+#[test]
+fn kind_overrides() {
+    let classifier = IoClassifier::new()
+        .kind(ErrorKind::NotFound, Severity::Fatal)
+        .kind(ErrorKind::PermissionDenied, Severity::Fatal)
+        .kind(ErrorKind::PermissionDenied, Severity::Error);
+    assert_eq!(classifier.kind_severity(ErrorKind::NotFound), Severity::Fatal);
+    assert_eq!(classifier.kind_severity(ErrorKind::PermissionDenied), Severity::Error);
+    assert_eq!(classifier.kind_severity(ErrorKind::TimedOut), Severity::Error);
+    assert!(classifier.classify(Error::new(ErrorKind::NotFound, "missing")).is_fatal());
+}
+
+#[test]
+fn os_error_overrides_take_precedence_over_kinds() {
+    // ENOENT on unix, ERROR_FILE_NOT_FOUND on windows
+    let code = 2;
+    let kind = Error::from_raw_os_error(code).kind();
+    let classifier = IoClassifier::new().kind(kind, Severity::Fatal);
+    assert_eq!(classifier.os_error_severity(code), Severity::Fatal);
+    let classifier = classifier.os_error(code, Severity::Error);
+    assert_eq!(classifier.os_error_severity(code), Severity::Error);
+    assert!(!classifier.classify_os_error(code).is_fatal());
+    // errors without an os code only look at their kind
+    assert!(classifier.classify_kind(kind).is_fatal());
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "alloc")]
+use fatal_error::{FatalError, FatalIteratorExt};
+
+type Item = Result<u32, FatalError<&'static str>>;
+
+fn items() -> Vec<Item> {
+    vec![
+        Ok(1),
+        Err(FatalError::Error("a")),
+        Ok(2),
+        Err(FatalError::Fatal("b")),
+        Ok(3),
+        Err(FatalError::Error("c")),
+    ]
+}
+
+#[test]
+fn stops_at_the_first_fatal_error() {
+    let mut source = items().into_iter();
+    let mut iter = source.by_ref().until_fatal();
+    assert_eq!(iter.by_ref().collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(iter.fatal(), Some(&"b"));
+    // the iterator is fused once a fatal error is seen
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.errors(), ["a"]);
+    drop(iter);
+    // items after the fatal error are left in the source
+    assert_eq!(source.collect::<Vec<_>>(), [Ok(3), Err(FatalError::Error("c"))]);
+}
+
+#[test]
+fn size_hint() {
+    let mut iter = items().into_iter().until_fatal();
+    assert_eq!(iter.size_hint(), (0, Some(6)));
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.size_hint(), (0, Some(5)));
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.next(), None);
+    // the source still has items but none will be yielded
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn finish_and_into_parts() {
+    let mut iter = items().into_iter().until_fatal();
+    iter.by_ref().for_each(drop);
+    assert_eq!(iter.clone().into_parts(), (vec!["a"], Some("b")));
+    assert_eq!(iter.finish(), Err("b"));
+
+    let mut iter = items().into_iter().take(3).until_fatal();
+    iter.by_ref().for_each(drop);
+    assert_eq!(iter.clone().into_parts(), (vec!["a"], None));
+    assert_eq!(iter.finish(), Ok(vec!["a"]));
+}
+
+#[test]
+fn take_errors() {
+    let mut iter = items().into_iter().until_fatal();
+    assert_eq!(iter.next(), Some(1));
+    assert!(iter.take_errors().is_empty());
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.take_errors(), ["a"]);
+    assert!(iter.errors().is_empty());
+    assert_eq!(iter.next(), None);
+    // taken errors are not returned again
+    assert_eq!(iter.into_parts(), (vec![], Some("b")));
+}
+
+#[test]
+fn partition_fatality() {
+    assert_eq!(
+        items().into_iter().partition_fatality(),
+        (vec![1, 2], vec!["a"], Some("b"))
+    );
+    let items = items().into_iter().filter(|x| !matches!(x, Err(FatalError::Fatal(_))));
+    assert_eq!(items.partition_fatality(), (vec![1, 2, 3], vec!["a", "c"], None));
+}
+
+#[test]
+fn collect_until_fatal() {
+    assert_eq!(items().into_iter().collect_until_fatal::<Vec<_>>(), Err("b"));
+    let items = items().into_iter().filter(|x| !matches!(x, Err(FatalError::Fatal(_))));
+    assert_eq!(
+        items.collect_until_fatal::<Vec<_>>(),
+        Ok((vec![1, 2, 3], vec!["a", "c"]))
+    );
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "log")]
+use common::FakeClock;
+use fatal_error::{
+    log::{FatalityLogger, LogResultExt},
+    FatalError,
+};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::{
+    sync::{Mutex, Once},
+    time::Duration,
+};
+
+mod common;
+
+/// Logger recording every record as (target, level, message)
+struct Recorder(Mutex<Vec<(String, Level, String)>>);
+
+impl Log for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool { true }
+
+    fn log(&self, record: &Record<'_>) {
+        let entry =
+            (record.target().to_string(), record.level(), record.args().to_string());
+        self.0.lock().unwrap().push(entry);
+    }
+
+    fn flush(&self) {}
+}
+
+static RECORDER: Recorder = Recorder(Mutex::new(Vec::new()));
+
+/// return the records of the given target, tests run in parallel and log to distinct targets
+fn records(target: &str) -> Vec<(Level, String)> {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&RECORDER).unwrap();
+        log::set_max_level(LevelFilter::Trace);
+    });
+    RECORDER
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(x, ..)| x == target)
+        .map(|(_, level, message)| (*level, message.clone()))
+        .collect()
+}
+
+fn error(x: &'static str) -> FatalError<&'static str> { FatalError::Error(x) }
+
+#[test]
+fn default_levels() {
+    records("default");
+    error("timeout").log_fatality("default");
+    let _ = Err::<(), _>(FatalError::<&str>::Fatal("disk full")).log_fatality("default");
+    assert_eq!(
+        records("default"),
+        [
+            (Level::Warn, "non fatal error: timeout".to_string()),
+            (Level::Error, "fatal error: disk full".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn custom_levels() {
+    records("levels");
+    let logger =
+        FatalityLogger::new("levels").error_level(Level::Info).fatal_level(Level::Warn);
+    logger.log(&error("timeout"));
+    logger.log(&FatalError::<&str>::Fatal("disk full"));
+    assert_eq!(
+        records("levels"),
+        [
+            (Level::Info, "non fatal error: timeout".to_string()),
+            (Level::Warn, "fatal error: disk full".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn rate_limit() {
+    records("rate");
+    let clock = FakeClock::new();
+    let logger =
+        FatalityLogger::with_clock("rate", &clock).rate_limit(Duration::from_secs(1));
+    logger.log(&error("a"));
+    logger.log(&error("b"));
+    let _ = Err::<(), _>(error("c")).inspect_logged(&logger);
+    // fatal errors are never suppressed
+    logger.log(&FatalError::<&str>::Fatal("disk full"));
+    clock.advance(Duration::from_millis(999));
+    logger.log(&error("d"));
+    clock.advance(Duration::from_millis(1));
+    logger.log(&error("e"));
+    logger.log(&error("f"));
+    clock.advance(Duration::from_secs(1));
+    logger.log(&error("g"));
+    assert_eq!(
+        records("rate"),
+        [
+            (Level::Warn, "non fatal error: a".to_string()),
+            (Level::Error, "fatal error: disk full".to_string()),
+            (
+                Level::Warn,
+                "non fatal error: e (3 non fatal error(s) suppressed)".to_string()
+            ),
+            (
+                Level::Warn,
+                "non fatal error: g (1 non fatal error(s) suppressed)".to_string()
+            ),
+        ]
+    );
+}
@@ -0,0 +1,75 @@
+#![cfg(any(feature = "anyhow", feature = "eyre"))]
+
+/// generates the marker tests of a report type
+///
+/// `$context` is the method of the report adding a context layer
+macro_rules! marker_tests {
+    ($krate:ident, $report:ty, $ext:ident, $context:ident) => {
+        mod $krate {
+            use fatal_error::{
+                $krate::{from_fatal_error, is_fatal, mark, severity, $ext},
+                FatalError, Severity,
+            };
+            use std::io;
+
+            fn report() -> $report {
+                <$report>::new(io::Error::other("connection refused"))
+            }
+
+            #[test]
+            fn marked_errors_keep_downcasting() {
+                let error = mark(report(), Severity::Fatal);
+                assert!(is_fatal(&error));
+                assert_eq!(
+                    error.downcast_ref::<io::Error>().unwrap().to_string(),
+                    "connection refused"
+                );
+                assert_eq!(format!("{error:#}"), "fatal error: connection refused");
+                let error = error.downcast::<io::Error>().unwrap();
+                assert_eq!(error.kind(), io::ErrorKind::Other);
+            }
+
+            #[test]
+            fn severity_survives_context() {
+                let error = Err::<(), _>(report())
+                    .recoverable()
+                    .unwrap_err()
+                    .$context("while loading config");
+                assert_eq!(severity(&error), Some(Severity::Error));
+                assert_eq!(error.downcast_ref::<&str>(), Some(&"while loading config"));
+                assert!(error.downcast_ref::<io::Error>().is_some());
+            }
+
+            #[test]
+            fn mark_replaces_the_outermost_marker() {
+                let error = Err::<(), _>(report()).fatal().recoverable().unwrap_err();
+                assert_eq!(severity(&error), Some(Severity::Error));
+                assert_eq!(error.chain().count(), 2);
+            }
+
+            #[test]
+            fn unmarked_errors() {
+                assert_eq!(severity(&report()), None);
+                let error = <$report>::new(FatalError::<io::Error>::Fatal(
+                    io::Error::other("disk full"),
+                ));
+                assert_eq!(severity(&error), Some(Severity::Fatal));
+            }
+
+            #[test]
+            fn conversion() {
+                let error = from_fatal_error(FatalError::<io::Error>::Error(
+                    io::Error::other("timeout"),
+                ));
+                assert_eq!(severity(&error), Some(Severity::Error));
+                assert!(error.downcast_ref::<io::Error>().is_some());
+            }
+        }
+    };
+}
+
+#[cfg(feature = "anyhow")]
+marker_tests!(anyhow, ::anyhow::Error, AnyhowResultExt, context);
+
+#[cfg(feature = "eyre")]
+marker_tests!(eyre, ::eyre::Report, EyreResultExt, wrap_err);
@@ -0,0 +1,147 @@
+use fatal_error::{try_fatal, try_recover, FatalError, FromFatal, Outcome};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Fatal(u32);
+
+/// payload wider than `Fatal`, built from it by `From`
+#[derive(Debug, PartialEq, Eq)]
+struct Fatal2(u32);
+
+impl From<Fatal> for Fatal2 {
+    fn from(x: Fatal) -> Self { Fatal2(x.0) }
+}
+
+impl From<Fatal> for u32 {
+    fn from(x: Fatal) -> Self { x.0 }
+}
+
+type O<T> = Outcome<T, u32, Fatal>;
+type R<T> = Result<T, FatalError<u32, Fatal>>;
+
+#[test]
+fn conversions() {
+    let cases: [(O<u8>, R<u8>); 3] = [
+        (Outcome::Ok(1), Ok(1)),
+        (Outcome::Error(2), Err(FatalError::Error(2))),
+        (Outcome::Fatal(Fatal(3)), Err(FatalError::Fatal(Fatal(3)))),
+    ];
+    for (outcome, result) in cases {
+        assert_eq!(Outcome::from(result), outcome);
+    }
+    assert_eq!(O::<u8>::Fatal(Fatal(3)).into_result(), Err(FatalError::Fatal(Fatal(3))));
+    assert_eq!(Result::from(O::<u8>::Error(2)), R::<u8>::Err(FatalError::Error(2)));
+}
+
+#[test]
+fn escalate_and_deescalate() {
+    let escalate = |x: Outcome<u8, Fatal, u32>| x.escalate();
+    assert_eq!(escalate(Outcome::Ok(1)), Outcome::Ok(1));
+    assert_eq!(escalate(Outcome::Error(Fatal(2))), Outcome::Fatal(2));
+    assert_eq!(escalate(Outcome::Fatal(3)), Outcome::Fatal(3));
+    let deescalate = |x: O<u8>| x.deescalate();
+    assert_eq!(deescalate(Outcome::Ok(1)), Outcome::Ok(1));
+    assert_eq!(deescalate(Outcome::Error(2)), Outcome::Error(2));
+    assert_eq!(deescalate(Outcome::Fatal(Fatal(3))), Outcome::Error(3));
+}
+
+#[test]
+fn unwrap_or_else() {
+    let unwrap = |x: O<u32>| {
+        x.unwrap_or_else(|x| match x {
+            FatalError::Error(x) => x + 10,
+            FatalError::Fatal(x) => x.0 + 20,
+        })
+    };
+    assert_eq!(unwrap(Outcome::Ok(1)), 1);
+    assert_eq!(unwrap(Outcome::Error(2)), 12);
+    assert_eq!(unwrap(Outcome::Fatal(Fatal(3))), 23);
+}
+
+/// propagates `input` with both macros, recording which of them fell through
+fn in_result(
+    input: O<u32>, steps: &mut Vec<&'static str>,
+) -> Result<u32, FatalError<u32, Fatal2>> {
+    let x = try_fatal!(input);
+    steps.push("try_fatal");
+    let y = try_recover!(input, |x| x + 100);
+    steps.push("try_recover");
+    assert_eq!(x.unwrap_or_else(|x| x + 100), y);
+    Ok(y)
+}
+
+/// same as `in_result` returning an `Outcome`
+fn in_outcome(input: O<u32>, steps: &mut Vec<&'static str>) -> Outcome<u32, u32, Fatal2> {
+    let x = try_fatal!(input);
+    steps.push("try_fatal");
+    let y = try_recover!(input, |x| x + 100);
+    steps.push("try_recover");
+    assert_eq!(x.unwrap_or_else(|x| x + 100), y);
+    Outcome::Ok(y)
+}
+
+#[test]
+fn macros_return_only_on_fatal() {
+    let mut steps = Vec::new();
+    assert_eq!(in_result(Outcome::Ok(1), &mut steps), Ok(1));
+    assert_eq!(steps, ["try_fatal", "try_recover"]);
+    steps.clear();
+    assert_eq!(in_result(Outcome::Error(2), &mut steps), Ok(102));
+    assert_eq!(steps, ["try_fatal", "try_recover"]);
+    steps.clear();
+    assert_eq!(
+        in_result(Outcome::Fatal(Fatal(3)), &mut steps),
+        Err(FatalError::Fatal(Fatal2(3)))
+    );
+    assert!(steps.is_empty());
+
+    let mut steps = Vec::new();
+    assert_eq!(in_outcome(Outcome::Ok(1), &mut steps), Outcome::Ok(1));
+    assert_eq!(steps, ["try_fatal", "try_recover"]);
+    steps.clear();
+    assert_eq!(in_outcome(Outcome::Error(2), &mut steps), Outcome::Ok(102));
+    assert_eq!(steps, ["try_fatal", "try_recover"]);
+    steps.clear();
+    assert_eq!(
+        in_outcome(Outcome::Fatal(Fatal(3)), &mut steps),
+        Outcome::Fatal(Fatal2(3))
+    );
+    assert!(steps.is_empty());
+}
+
+#[test]
+fn try_recover_returns_fatal_errors() {
+    fn recover(input: O<u32>) -> Outcome<u32, u32, Fatal2> {
+        Outcome::Ok(try_recover!(input, |x| x + 100))
+    }
+    assert_eq!(recover(Outcome::Error(2)), Outcome::Ok(102));
+    assert_eq!(recover(Outcome::Fatal(Fatal(3))), Outcome::Fatal(Fatal2(3)));
+}
+
+#[test]
+fn macros_accept_results() {
+    fn run(input: R<u32>) -> Result<Result<u32, u32>, FatalError<u32>> {
+        Ok(try_fatal!(input))
+    }
+    assert_eq!(run(Ok(1)), Ok(Ok(1)));
+    assert_eq!(run(Err(FatalError::Error(2))), Ok(Err(2)));
+    assert_eq!(run(Err(FatalError::Fatal(Fatal(3)))), Err(FatalError::Fatal(3)));
+
+    fn recover(input: R<u32>) -> Outcome<u32, u32> {
+        Outcome::Ok(try_recover!(input, |x| x + 100))
+    }
+    assert_eq!(recover(Ok(1)), Outcome::Ok(1));
+    assert_eq!(recover(Err(FatalError::Error(2))), Outcome::Ok(102));
+    assert_eq!(recover(Err(FatalError::Fatal(Fatal(3)))), Outcome::Fatal(3));
+}
+
+#[test]
+fn from_fatal_converts_the_payload() {
+    assert_eq!(
+        Result::<(), FatalError<u32, Fatal2>>::from_fatal(Fatal(1)),
+        Err(FatalError::Fatal(Fatal2(1)))
+    );
+    assert_eq!(
+        Outcome::<(), u32, Fatal2>::from_fatal(Fatal(1)),
+        Outcome::Fatal(Fatal2(1))
+    );
+}
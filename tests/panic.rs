@@ -0,0 +1,91 @@
+#![cfg(feature = "std")]
+use fatal_error::panic::catch;
+use std::panic::{catch_unwind, panic_any, resume_unwind};
+
+#[test]
+fn returns_the_value() {
+    assert_eq!(catch(|| 42).unwrap(), 42);
+}
+
+#[test]
+fn converts_panics() {
+    let error = catch(|| panic!("boom {}", 42)).unwrap_err();
+    assert!(error.is_fatal());
+    let error = error.into_inner();
+    assert_eq!(error.message(), "boom 42");
+    assert!(error.location().unwrap().starts_with(file!()), "{error}");
+    assert!(error.to_string().ends_with(": boom 42"));
+
+    let error = catch(|| panic!("static")).unwrap_err().into_inner();
+    assert_eq!(error.message(), "static");
+    let error = catch(|| panic_any(42)).unwrap_err().into_inner();
+    assert_eq!(error.message(), "Box<dyn Any>");
+    assert!(error.location().is_some());
+}
+
+#[test]
+fn panics_caught_inside_are_not_recorded() {
+    let value = catch(|| {
+        let inner = catch_unwind(|| panic!("inner"));
+        assert!(inner.is_err());
+        1
+    });
+    assert_eq!(value.unwrap(), 1);
+
+    // a resumed panic does not run the hook, the record of the inner one is not reused
+    let error = catch(|| {
+        let inner = catch_unwind(|| panic!("inner")).unwrap_err();
+        let _ = catch_unwind(|| panic!("other"));
+        resume_unwind(inner)
+    })
+    .unwrap_err()
+    .into_inner();
+    assert_eq!(error.message(), "inner");
+    assert_eq!(error.location(), None);
+}
+
+#[test]
+fn nested_catch() {
+    let outer = catch(|| {
+        let inner = catch(|| panic!("inner")).unwrap_err().into_inner();
+        assert_eq!(inner.message(), "inner");
+        panic!("outer")
+    })
+    .unwrap_err()
+    .into_inner();
+    assert_eq!(outer.message(), "outer");
+    assert!(outer.location().is_some());
+}
+
+#[test]
+fn catch_in_a_destructor_while_unwinding() {
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) { assert!(catch(|| panic!("drop")).is_err()) }
+    }
+
+    let error = catch(|| {
+        let _guard = Guard;
+        panic!("outer")
+    })
+    .unwrap_err()
+    .into_inner();
+    assert_eq!(error.message(), "outer");
+    assert!(error.location().is_some());
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn catch_future() {
+    use fatal_error::panic::catch_future;
+    use futures::FutureExt;
+
+    let value = catch_future(async { 42 }).now_or_never().unwrap();
+    assert_eq!(value.unwrap(), 42);
+    let error =
+        catch_future(async { panic!("async") }).now_or_never().unwrap().unwrap_err();
+    assert_eq!(error.into_inner().message(), "async");
+    let mut pending = std::pin::pin!(catch_future(futures::future::pending::<()>()));
+    assert!(pending.as_mut().now_or_never().is_none());
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "policy")]
+use fatal_error::{
+    policy::{Action, Matcher, Policy},
+    FatalError, Severity,
+};
+use regex::Regex;
+use std::{
+    error::Error as StdError,
+    fmt, io,
+    num::{IntErrorKind, ParseIntError},
+};
+
+const POLICY: &str = r#"
+[[rules]]
+match = { io_kind = "ConnectionRefused" }
+action = "deescalate"
+
+[[rules]]
+match = { type_name = "ParseIntError", regex = "^invalid digit" }
+action = "escalate"
+
+[[rules]]
+match = { severity = "fatal", contains = "keep" }
+action = "keep"
+
+[[rules]]
+match = { severity = "fatal" }
+action = "deescalate"
+"#;
+
+/// error wrapping an io error as its source
+#[derive(Debug)]
+struct Request(io::Error);
+
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("request failed")
+    }
+}
+
+impl StdError for Request {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> { Some(&self.0) }
+}
+
+fn parse_error(input: &str) -> ParseIntError { input.parse::<u8>().unwrap_err() }
+
+#[test]
+fn load_toml() {
+    let policy: Policy = toml::from_str(POLICY).unwrap();
+    assert_eq!(policy.rules().len(), 4);
+    assert_eq!(policy.rules()[0].action(), Action::Deescalate);
+
+    let refused = io::Error::from(io::ErrorKind::ConnectionRefused);
+    assert!(!policy.apply(FatalError::Fatal(refused)).is_fatal());
+    // the io error is looked for in the sources
+    let request = Request(io::Error::from(io::ErrorKind::ConnectionRefused));
+    assert_eq!(policy.decide(&FatalError::Fatal(request)), Action::Deescalate);
+
+    let invalid = parse_error("x");
+    assert_eq!(*invalid.kind(), IntErrorKind::InvalidDigit);
+    assert!(policy.apply(FatalError::Error(invalid)).is_fatal());
+    // too large is not an invalid digit, it is kept as is
+    assert!(!policy.apply(FatalError::Error(parse_error("300"))).is_fatal());
+
+    let keep = io::Error::other("keep me");
+    assert!(policy.apply(FatalError::Fatal(keep)).is_fatal());
+    let other = io::Error::other("disk full");
+    assert!(!policy.apply(FatalError::Fatal(other)).is_fatal());
+}
+
+#[test]
+fn round_trip() {
+    let policy: Policy = toml::from_str(POLICY).unwrap();
+    let json = serde_json::to_value(&policy).unwrap();
+    assert_eq!(
+        json["rules"][1]["match"],
+        serde_json::json!({"type_name": "ParseIntError", "regex": "^invalid digit"})
+    );
+    let policy: Policy = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        serde_json::to_string(&policy).unwrap(),
+        serde_json::to_string(&toml::from_str::<Policy>(POLICY).unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn rejects_invalid_configuration() {
+    let error = toml::from_str::<Policy>(
+        "[[rules]]\nmatch = { io_kind = \"ConnectionRefuzed\" }\naction = \"escalate\"",
+    )
+    .unwrap_err();
+    assert!(
+        error.to_string().contains("unknown io error kind `ConnectionRefuzed`"),
+        "{error}"
+    );
+    assert!(toml::from_str::<Policy>(
+        "[[rules]]\nmatch = { regex = \"(\" }\naction = \"escalate\""
+    )
+    .is_err());
+    assert!(toml::from_str::<Policy>(
+        "[[rules]]\nmatch = { name = \"x\" }\naction = \"escalate\""
+    )
+    .is_err());
+}
+
+#[test]
+fn builder() {
+    let policy = Policy::new()
+        .rule(Matcher::new().io_kind(io::ErrorKind::TimedOut), Action::Deescalate)
+        .rule(Matcher::new().code(13), Action::Escalate)
+        .rule(
+            Matcher::new()
+                .severity(Severity::Error)
+                .regex(Regex::new("^fatal:").unwrap()),
+            Action::Escalate,
+        );
+    let timed_out = io::Error::from(io::ErrorKind::TimedOut);
+    assert!(!policy.apply(FatalError::Fatal(timed_out)).is_fatal());
+    assert!(policy.apply(FatalError::Error(io::Error::from_raw_os_error(13))).is_fatal());
+    assert!(policy.apply(FatalError::Error(io::Error::other("fatal: bad"))).is_fatal());
+    assert_eq!(
+        policy.decide(&FatalError::Fatal(io::Error::other("fatal: bad"))),
+        Action::Keep
+    );
+    assert_eq!(
+        Policy::new().decide(&FatalError::Error(io::Error::other("x"))),
+        Action::Keep
+    );
+}
+
+#[test]
+fn type_name_checks_the_wrapped_type_only() {
+    let matcher = Matcher::new().type_name("ParseIntError");
+    assert!(matcher.matches(&FatalError::Error(parse_error("x"))));
+    assert!(Matcher::new()
+        .type_name("core::num::error::ParseIntError")
+        .matches(&FatalError::Error(parse_error("x"))));
+    assert!(!Matcher::new()
+        .type_name("IntError")
+        .matches(&FatalError::Error(parse_error("x"))));
+    // the io error is only a source of the request error
+    let request = FatalError::Error(Request(io::Error::other("disk full")));
+    assert!(Matcher::new().type_name("Request").matches(&request));
+    assert!(!Matcher::new()
+        .type_name(std::any::type_name::<io::Error>())
+        .matches(&request));
+}
@@ -0,0 +1,55 @@
+use fatal_error::{FatalError, FatalResultExt, IntoFatalResultExt};
+
+#[derive(Debug, PartialEq, Eq)]
+struct Fatal(u32);
+
+impl From<Fatal> for u32 {
+    fn from(x: Fatal) -> Self { x.0 }
+}
+
+type Result<T> = core::result::Result<T, FatalError<u32, Fatal>>;
+
+#[test]
+fn or_recover() {
+    assert_eq!(Result::Ok(1).or_recover(|x| x + 1), Ok(1));
+    assert_eq!(Result::Err(FatalError::Error(2)).or_recover(|x| x + 1), Ok(3));
+    assert_eq!(
+        Result::Err(FatalError::Fatal(Fatal(2))).or_recover(|_| unreachable!()),
+        Err(Fatal(2))
+    );
+}
+
+#[test]
+fn ok_or_fatal() {
+    assert_eq!(Result::Ok(1).ok_or_fatal(), Ok(Some(1)));
+    assert_eq!(Result::<u32>::Err(FatalError::Error(2)).ok_or_fatal(), Ok(None));
+    assert_eq!(
+        Result::<u32>::Err(FatalError::Fatal(Fatal(2))).ok_or_fatal(),
+        Err(Fatal(2))
+    );
+}
+
+#[test]
+fn into_fatal_if() {
+    let classify = |x: core::result::Result<u32, u32>| x.into_fatal_if(|x| *x > 1);
+    assert_eq!(classify(Ok(5)), Ok(5));
+    assert_eq!(classify(Err(1)), Err(FatalError::Error(1)));
+    assert_eq!(classify(Err(2)), Err(FatalError::Fatal(2)));
+    assert_eq!(Err::<(), _>(1).into_error(), Err(FatalError::Error(1)));
+    assert_eq!(Err::<(), _>(1).into_fatal(), Err(FatalError::Fatal(1)));
+}
+
+#[test]
+fn map_inner_err_converts_the_fatal_payload() {
+    let map = |x: Result<()>| x.map_inner_err(|x| x * 10);
+    assert_eq!(map(Ok(())), Ok(()));
+    assert_eq!(map(Err(FatalError::Error(1))), Err(FatalError::Error(10)));
+    assert_eq!(map(Err(FatalError::Fatal(Fatal(2)))), Err(FatalError::Fatal(20)));
+}
+
+#[test]
+fn into_inner_err_converts_the_fatal_payload() {
+    assert_eq!(Result::Ok(1).into_inner_err(), Ok(1));
+    assert_eq!(Result::<()>::Err(FatalError::Error(1)).into_inner_err(), Err(1));
+    assert_eq!(Result::<()>::Err(FatalError::Fatal(Fatal(2))).into_inner_err(), Err(2));
+}
@@ -0,0 +1,123 @@
+#![cfg(feature = "std")]
+use common::FakeClock;
+use fatal_error::{
+    retry::{Backoff, RetryPolicy, RetryStop},
+    FatalError,
+};
+use std::time::Duration;
+
+mod common;
+
+fn ms(x: u64) -> Duration { Duration::from_millis(x) }
+
+fn fail() -> Result<(), FatalError<()>> { Err(FatalError::Error(())) }
+
+#[test]
+fn stops_on_fatal() {
+    let clock = FakeClock::new();
+    let mut calls = 0;
+    let error = RetryPolicy::new()
+        .max_attempts(10)
+        .retry_with_clock(&clock, || {
+            calls += 1;
+            if calls < 3 {
+                Err::<(), _>(FatalError::Error(calls))
+            } else {
+                Err(FatalError::Fatal(calls))
+            }
+        })
+        .unwrap_err();
+    assert_eq!(error.reason(), RetryStop::Fatal);
+    assert!(error.is_fatal());
+    assert_eq!(error.attempts(), 3);
+    assert_eq!(error.last(), Some(&FatalError::Fatal(3)));
+    assert_eq!(clock.sleeps().len(), 2);
+}
+
+#[test]
+fn succeeds_after_errors() {
+    let clock = FakeClock::new();
+    let mut calls = 0;
+    let value = RetryPolicy::new()
+        .retry_with_clock(&clock, || {
+            calls += 1;
+            if calls < 3 {
+                fail().map(|_| 0)
+            } else {
+                Ok(calls)
+            }
+        })
+        .unwrap();
+    assert_eq!(value, 3);
+}
+
+#[test]
+fn exhausts_max_attempts() {
+    let clock = FakeClock::new();
+    let error = RetryPolicy::new()
+        .max_attempts(4)
+        .backoff(Backoff::Fixed(ms(10)))
+        .retry_with_clock(&clock, fail)
+        .unwrap_err();
+    assert_eq!(error.reason(), RetryStop::Exhausted);
+    assert_eq!(error.attempts(), 4);
+    assert_eq!(clock.sleeps(), vec![ms(10); 3]);
+}
+
+#[test]
+fn stops_before_deadline() {
+    let clock = FakeClock::new();
+    let error = RetryPolicy::new()
+        .unlimited_attempts()
+        .backoff(Backoff::Fixed(ms(30)))
+        .deadline(ms(100))
+        .retry_with_clock(&clock, fail)
+        .unwrap_err();
+    assert_eq!(error.reason(), RetryStop::DeadlineExceeded);
+    // attempts at 0, 30, 60 and 90ms, the next one would start at 120ms
+    assert_eq!(error.attempts(), 4);
+    assert_eq!(clock.sleeps(), vec![ms(30); 3]);
+}
+
+#[test]
+fn exponential_is_capped() {
+    let clock = FakeClock::new();
+    let backoff = Backoff::Exponential { initial: ms(10), factor: 3, max: ms(200) };
+    RetryPolicy::new()
+        .max_attempts(6)
+        .backoff(backoff)
+        .retry_with_clock(&clock, fail)
+        .unwrap_err();
+    assert_eq!(clock.sleeps(), vec![ms(10), ms(30), ms(90), ms(200), ms(200)]);
+    assert_eq!(backoff.max_delay(u32::MAX), ms(200));
+}
+
+#[test]
+fn seeded_jitter_is_bounded_and_reproducible() {
+    let backoff = Backoff::Jittered { initial: ms(10), factor: 2, max: ms(100) };
+    let policy = RetryPolicy::new().max_attempts(20).backoff(backoff).jitter_seed(42);
+    let run = || {
+        let clock = FakeClock::new();
+        policy.retry_with_clock(&clock, fail).unwrap_err();
+        clock.sleeps()
+    };
+    let sleeps = run();
+    assert_eq!(sleeps.len(), 19);
+    for (i, x) in sleeps.iter().enumerate() {
+        assert!(*x <= backoff.max_delay(i as u32 + 1), "{x:?} after attempt {}", i + 1);
+    }
+    assert_eq!(sleeps, run());
+}
+
+#[test]
+fn display_leaves_the_last_error_to_source() {
+    let clock = FakeClock::new();
+    let error = RetryPolicy::new()
+        .max_attempts(2)
+        .retry_with_clock(&clock, || {
+            Err::<(), _>(FatalError::<_>::Error(std::io::Error::other("boom")))
+        })
+        .unwrap_err();
+    assert_eq!(error.to_string(), "attempts exhausted after 2 attempt(s)");
+    assert_eq!(std::error::Error::source(&error).unwrap().to_string(), "Error: boom");
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "tokio")]
+use fatal_error::{
+    retry::{
+        tokio::{retry, retry_cancellable},
+        Backoff, RetryPolicy, RetryStop,
+    },
+    FatalError,
+};
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+use tokio_util::sync::CancellationToken;
+
+fn ms(x: u64) -> Duration { Duration::from_millis(x) }
+
+async fn fail() -> Result<(), FatalError<()>> { Err(FatalError::Error(())) }
+
+#[tokio::test(start_paused = true)]
+async fn waits_the_backoff_between_attempts() {
+    let start = Instant::now();
+    let policy = RetryPolicy::new().max_attempts(4).backoff(Backoff::Exponential {
+        initial: ms(100),
+        factor:  2,
+        max:     ms(300),
+    });
+    let error = retry(&policy, fail).await.unwrap_err();
+    assert_eq!(error.reason(), RetryStop::Exhausted);
+    assert_eq!(error.attempts(), 4);
+    assert_eq!(start.elapsed(), ms(100 + 200 + 300));
+}
+
+#[tokio::test(start_paused = true)]
+async fn stops_on_fatal() {
+    let mut calls = 0;
+    let error = retry(&RetryPolicy::new().max_attempts(10), || {
+        calls += 1;
+        let fatal = calls == 2;
+        async move {
+            if fatal {
+                Err::<(), _>(FatalError::Fatal(()))
+            } else {
+                fail().await
+            }
+        }
+    })
+    .await
+    .unwrap_err();
+    assert_eq!(error.reason(), RetryStop::Fatal);
+    assert_eq!(error.attempts(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn stops_before_deadline() {
+    let start = Instant::now();
+    let policy = RetryPolicy::new()
+        .unlimited_attempts()
+        .backoff(Backoff::Fixed(ms(30)))
+        .deadline(ms(100));
+    let error = retry(&policy, || async {
+        sleep(ms(5)).await;
+        fail().await
+    })
+    .await
+    .unwrap_err();
+    assert_eq!(error.reason(), RetryStop::DeadlineExceeded);
+    // attempts start at 0, 35, 70ms, the next one would start after 105ms
+    assert_eq!(error.attempts(), 3);
+    assert_eq!(start.elapsed(), ms(3 * 5 + 2 * 30));
+}
+
+#[tokio::test(start_paused = true)]
+async fn cancelled_during_an_attempt() {
+    let start = Instant::now();
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        sleep(ms(500)).await;
+        canceller.cancel();
+    });
+    let error = retry_cancellable(&RetryPolicy::new(), &token, || async {
+        sleep(ms(1000)).await;
+        fail().await
+    })
+    .await
+    .unwrap_err();
+    assert_eq!(error.reason(), RetryStop::Cancelled);
+    assert_eq!(error.attempts(), 0);
+    assert_eq!(start.elapsed(), ms(500));
+}
+
+#[tokio::test(start_paused = true)]
+async fn cancelled_during_the_backoff() {
+    let start = Instant::now();
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        sleep(ms(1000)).await;
+        canceller.cancel();
+    });
+    let policy = RetryPolicy::new().backoff(Backoff::Fixed(Duration::from_secs(10)));
+    let error = retry_cancellable(&policy, &token, fail).await.unwrap_err();
+    assert_eq!(error.reason(), RetryStop::Cancelled);
+    assert_eq!(error.attempts(), 1);
+    assert_eq!(start.elapsed(), ms(1000));
+}
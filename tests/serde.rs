@@ -0,0 +1,76 @@
+#![cfg(feature = "serde")]
+use fatal_error::{FatalError, Severity};
+use serde::{Deserialize, Serialize};
+use serde_json::{from_value, json, to_value};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Detailed {
+    code:    u16,
+    message: String,
+}
+
+#[test]
+fn adjacently_tagged_by_default() {
+    let error = FatalError::<String>::Fatal("disk full".into());
+    let value = to_value(&error).unwrap();
+    assert_eq!(value, json!({"severity": "fatal", "error": "disk full"}));
+    assert_eq!(from_value::<FatalError<String>>(value).unwrap(), error);
+
+    let error = FatalError::<String>::Error("timeout".into());
+    let value = to_value(&error).unwrap();
+    assert_eq!(value, json!({"severity": "error", "error": "timeout"}));
+    assert_eq!(from_value::<FatalError<String>>(value).unwrap(), error);
+}
+
+#[test]
+fn distinct_fatal_payload() {
+    let error = FatalError::<String, Detailed>::Fatal(Detailed {
+        code:    507,
+        message: "disk full".into(),
+    });
+    let value = to_value(&error).unwrap();
+    assert_eq!(
+        value,
+        json!({"severity": "fatal", "error": {"code": 507, "message": "disk full"}})
+    );
+    assert_eq!(from_value::<FatalError<String, Detailed>>(value).unwrap(), error);
+
+    let error = FatalError::<String, Detailed>::Error("timeout".into());
+    let value = to_value(&error).unwrap();
+    assert_eq!(value, json!({"severity": "error", "error": "timeout"}));
+    assert_eq!(from_value::<FatalError<String, Detailed>>(value).unwrap(), error);
+
+    // the payload must match the severity
+    assert!(from_value::<FatalError<String, Detailed>>(
+        json!({"severity": "fatal", "error": "disk full"})
+    )
+    .is_err());
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Report {
+    #[serde(with = "fatal_error::serde::externally_tagged")]
+    last: FatalError<String, Detailed>,
+}
+
+#[test]
+fn externally_tagged() {
+    let report = Report { last: FatalError::Error("timeout".into()) };
+    let value = to_value(&report).unwrap();
+    assert_eq!(value, json!({"last": {"error": "timeout"}}));
+    assert_eq!(from_value::<Report>(value).unwrap(), report);
+
+    let report =
+        Report { last: FatalError::Fatal(Detailed { code: 1, message: "bad".into() }) };
+    let value = to_value(&report).unwrap();
+    assert_eq!(value, json!({"last": {"fatal": {"code": 1, "message": "bad"}}}));
+    assert_eq!(from_value::<Report>(value).unwrap(), report);
+
+    assert!(from_value::<Report>(json!({"last": {"warning": "timeout"}})).is_err());
+}
+
+#[test]
+fn severity() {
+    assert_eq!(to_value(Severity::Fatal).unwrap(), json!("fatal"));
+    assert_eq!(from_value::<Severity>(json!("error")).unwrap(), Severity::Error);
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "futures")]
+use fatal_error::{FatalError, FatalStreamExt};
+use futures::{future::ready, stream, stream::FusedStream, FutureExt, StreamExt};
+use std::pin::pin;
+
+type Item = Result<u8, FatalError<&'static str>>;
+
+fn items() -> Vec<Item> {
+    vec![
+        Ok(1),
+        Err(FatalError::Error("a")),
+        Ok(2),
+        Err(FatalError::Error("b")),
+        Err(FatalError::Fatal("c")),
+        Ok(3),
+    ]
+}
+
+#[test]
+fn try_for_each_until_fatal() {
+    let mut errors = Vec::new();
+    let mut values = Vec::new();
+    let result = stream::iter(items())
+        .try_for_each_until_fatal(
+            |x| errors.push(x),
+            |x| {
+                values.push(x);
+                ready(Ok(()))
+            },
+        )
+        .now_or_never()
+        .unwrap();
+    assert_eq!(result, Err("c"));
+    assert_eq!(values, [1, 2]);
+    assert_eq!(errors, ["a", "b"]);
+}
+
+#[test]
+fn try_for_each_until_fatal_handles_errors_of_f() {
+    let mut errors = Vec::new();
+    let mut values = Vec::new();
+    let result = stream::iter([Ok(1), Ok(2), Ok(3), Ok(4)])
+        .try_for_each_until_fatal(
+            |x| errors.push(x),
+            |x: u8| {
+                values.push(x);
+                ready(match x {
+                    2 => Err(FatalError::Error("two")),
+                    3 => Err(FatalError::Fatal("three")),
+                    _ => Ok(()),
+                })
+            },
+        )
+        .now_or_never()
+        .unwrap();
+    assert_eq!(result, Err("three"));
+    assert_eq!(values, [1, 2, 3]);
+    assert_eq!(errors, ["two"]);
+}
+
+#[test]
+fn try_for_each_until_fatal_completes() {
+    let result = stream::iter([Ok(1), Err(FatalError::Error("a"))])
+        .try_for_each_until_fatal(|_| {}, |_: u8| ready(Ok::<_, FatalError<_>>(())))
+        .now_or_never()
+        .unwrap();
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn filter_recoverable() {
+    let mut errors = Vec::new();
+    let values: Vec<_> = stream::iter(items())
+        .filter_recoverable(|x| errors.push(x))
+        .collect()
+        .now_or_never()
+        .unwrap();
+    assert_eq!(values, [Ok(1), Ok(2), Err("c"), Ok(3)]);
+    assert_eq!(errors, ["a", "b"]);
+}
+
+#[test]
+fn take_until_fatal() {
+    let errors = std::cell::RefCell::new(Vec::new());
+    let mut stream =
+        pin!(stream::iter(items()).take_until_fatal(|x| errors.borrow_mut().push(x)));
+    let values: Vec<_> = stream.by_ref().collect().now_or_never().unwrap();
+    assert_eq!(values, [1, 2]);
+    assert!(stream.is_terminated());
+    assert_eq!(stream.fatal(), Some(&"c"));
+    assert_eq!(stream.as_mut().take_fatal(), Some("c"));
+    assert_eq!(stream.fatal(), None);
+    assert_eq!(stream.next().now_or_never(), Some(None));
+    assert_eq!(*errors.borrow(), ["a", "b"]);
+}
+
+#[test]
+fn take_until_fatal_without_fatal() {
+    let mut stream = pin!(stream::iter([Ok(1), Err(FatalError::Error("a")), Ok(2)])
+        .take_until_fatal(|_| {}));
+    let values: Vec<u8> = stream.by_ref().collect().now_or_never().unwrap();
+    assert_eq!(values, [1, 2]);
+    assert_eq!(stream.as_mut().take_fatal(), None::<&str>);
+}
@@ -0,0 +1,225 @@
+#![cfg(feature = "std")]
+use fatal_error::{
+    retry::Backoff,
+    supervisor::{ChildExit, ShutdownReason, Strategy, Supervisor},
+    FatalError,
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+type Result = std::result::Result<(), FatalError<&'static str>>;
+
+fn supervisor(strategy: Strategy) -> Supervisor<&'static str> {
+    Supervisor::new(strategy)
+        .max_restarts(10, Duration::from_secs(60))
+        .backoff(Backoff::Fixed(Duration::ZERO))
+}
+
+fn wait_until(condition: impl Fn() -> bool) {
+    while !condition() {
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    flaky:  AtomicUsize,
+    steady: AtomicUsize,
+    done:   AtomicBool,
+}
+
+/// runs a worker failing twice before succeeding next to a worker running until it is
+/// stopped or the first one succeeds, return how many times each one was started
+fn restarts(strategy: Strategy, flaky_first: bool) -> (usize, usize) {
+    let counters = Arc::new(Counters::default());
+    // whether the steady worker is restarted along with the flaky one
+    let siblings = match strategy {
+        Strategy::OneForOne => false,
+        Strategy::OneForAll => true,
+        Strategy::RestForOne => flaky_first,
+    };
+    let c = counters.clone();
+    let flaky = move |_: &_| -> Result {
+        let n = c.flaky.fetch_add(1, Ordering::SeqCst);
+        // fail once the steady worker runs, to make the number of its starts deterministic
+        let started = if siblings { n + 1 } else { 1 };
+        wait_until(|| c.steady.load(Ordering::SeqCst) >= started);
+        if n < 2 {
+            Err(FatalError::Error("flaky"))
+        } else {
+            c.done.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    };
+    let c = counters.clone();
+    let steady = move |token: &fatal_error::supervisor::StopToken| -> Result {
+        c.steady.fetch_add(1, Ordering::SeqCst);
+        wait_until(|| token.is_stopped() || c.done.load(Ordering::SeqCst));
+        Ok(())
+    };
+    let supervisor = supervisor(strategy);
+    let report = if flaky_first {
+        supervisor.child("flaky", flaky).child("steady", steady).run()
+    } else {
+        supervisor.child("steady", steady).child("flaky", flaky).run()
+    };
+    assert_eq!(report.reason(), ShutdownReason::Completed);
+    assert!(matches!(report.exit("flaky"), Some(ChildExit::Completed)));
+    assert!(matches!(report.exit("steady"), Some(ChildExit::Completed)));
+    (counters.flaky.load(Ordering::SeqCst), counters.steady.load(Ordering::SeqCst))
+}
+
+#[test]
+fn one_for_one() {
+    assert_eq!(restarts(Strategy::OneForOne, true), (3, 1));
+}
+
+#[test]
+fn one_for_all() {
+    assert_eq!(restarts(Strategy::OneForAll, true), (3, 3));
+    assert_eq!(restarts(Strategy::OneForAll, false), (3, 3));
+}
+
+#[test]
+fn rest_for_one() {
+    assert_eq!(restarts(Strategy::RestForOne, true), (3, 3));
+    assert_eq!(restarts(Strategy::RestForOne, false), (3, 1));
+}
+
+#[test]
+fn restart_intensity() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let c = calls.clone();
+    let report = Supervisor::new(Strategy::OneForOne)
+        .max_restarts(2, Duration::from_secs(60))
+        .backoff(Backoff::Fixed(Duration::ZERO))
+        .child("failing", move |_| -> Result {
+            c.fetch_add(1, Ordering::SeqCst);
+            Err(FatalError::Error("failing"))
+        })
+        .child("idle", |token| -> Result {
+            wait_until(|| token.is_stopped());
+            Ok(())
+        })
+        .run();
+    assert_eq!(report.reason(), ShutdownReason::RestartIntensity);
+    assert!(report.is_fatal());
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert!(matches!(report.exit("failing"), Some(ChildExit::GaveUp("failing"))));
+    assert!(matches!(report.exit("idle"), Some(ChildExit::Stopped)));
+}
+
+#[test]
+fn fatal_errors_and_panics_stop_siblings() {
+    let report = supervisor(Strategy::OneForAll)
+        .child("idle", |token| -> Result {
+            wait_until(|| token.is_stopped());
+            Ok(())
+        })
+        .child("fatal", |_| -> Result { Err(FatalError::Fatal("fatal")) })
+        .run();
+    assert_eq!(report.reason(), ShutdownReason::Fatal);
+    assert!(matches!(report.exit("idle"), Some(ChildExit::Stopped)));
+    assert!(matches!(report.exit("fatal"), Some(ChildExit::Fatal("fatal"))));
+
+    let report = supervisor(Strategy::OneForAll)
+        .child("idle", |token| -> Result {
+            wait_until(|| token.is_stopped());
+            Ok(())
+        })
+        .child("panic", |_| -> Result { panic!("boom") })
+        .run();
+    assert_eq!(report.reason(), ShutdownReason::Fatal);
+    assert!(matches!(report.exit("idle"), Some(ChildExit::Stopped)));
+    match report.exit("panic") {
+        Some(ChildExit::Panicked(x)) => assert_eq!(x.message(), "boom"),
+        x => panic!("unexpected exit {x:?}"),
+    }
+}
+
+#[test]
+fn failures_of_stopped_workers_are_kept() {
+    // a fatal error while stopping halts the restart of its siblings
+    let started = Arc::new(AtomicBool::new(false));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let (s, c) = (started.clone(), calls.clone());
+    let report = supervisor(Strategy::OneForAll)
+        .child("flaky", move |_| -> Result {
+            c.fetch_add(1, Ordering::SeqCst);
+            wait_until(|| s.load(Ordering::SeqCst));
+            Err(FatalError::Error("flaky"))
+        })
+        .child("shutdown", {
+            let s = started.clone();
+            move |token| -> Result {
+                s.store(true, Ordering::SeqCst);
+                wait_until(|| token.is_stopped());
+                Err(FatalError::Fatal("shutdown failed"))
+            }
+        })
+        .run();
+    assert_eq!(report.reason(), ShutdownReason::Fatal);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(matches!(report.exit("flaky"), Some(ChildExit::Failed("flaky"))));
+    assert!(matches!(report.exit("shutdown"), Some(ChildExit::Fatal("shutdown failed"))));
+
+    // a non fatal error while stopping is kept and the worker is not restarted
+    let starts = Arc::new(AtomicUsize::new(0));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let (s, c) = (starts.clone(), calls.clone());
+    let report = supervisor(Strategy::OneForAll)
+        .child("flaky", move |_| -> Result {
+            if c.fetch_add(1, Ordering::SeqCst) > 0 {
+                return Ok(());
+            }
+            wait_until(|| s.load(Ordering::SeqCst) > 0);
+            Err(FatalError::Error("flaky"))
+        })
+        .child("interrupted", {
+            let s = starts.clone();
+            move |token| -> Result {
+                s.fetch_add(1, Ordering::SeqCst);
+                wait_until(|| token.is_stopped());
+                Err(FatalError::Error("interrupted"))
+            }
+        })
+        .run();
+    assert_eq!(report.reason(), ShutdownReason::Completed);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(starts.load(Ordering::SeqCst), 1);
+    assert!(matches!(report.exit("flaky"), Some(ChildExit::Completed)));
+    assert!(matches!(report.exit("interrupted"), Some(ChildExit::Failed("interrupted"))));
+}
+
+#[test]
+fn stopping_interrupts_the_backoff() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let c = calls.clone();
+    let start = Instant::now();
+    // the failure of `flaky` only affects itself, the fatal error of `fatal` stops both
+    let report = Supervisor::new(Strategy::RestForOne)
+        .backoff(Backoff::Fixed(Duration::from_secs(60)))
+        .child("fatal", move |_| -> Result {
+            wait_until(|| c.load(Ordering::SeqCst) > 0);
+            thread::sleep(Duration::from_millis(50));
+            Err(FatalError::Fatal("fatal"))
+        })
+        .child("flaky", {
+            let c = calls.clone();
+            move |_| -> Result {
+                c.fetch_add(1, Ordering::SeqCst);
+                Err(FatalError::Error("flaky"))
+            }
+        })
+        .run();
+    assert!(start.elapsed() < Duration::from_secs(10), "{:?}", start.elapsed());
+    assert_eq!(report.reason(), ShutdownReason::Fatal);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(matches!(report.exit("flaky"), Some(ChildExit::Stopped)));
+}
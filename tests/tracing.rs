@@ -0,0 +1,154 @@
+#![cfg(feature = "tracing")]
+use fatal_error::{
+    tracing::{FatalSpanExt, TracedResultExt},
+    FatalError,
+};
+use std::{
+    error::Error as StdError,
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    subscriber::{with_default, Subscriber},
+    Event, Level, Metadata,
+};
+
+type Fields = Vec<(String, String)>;
+
+/// Subscriber recording the level and the fields of events and span records
+#[derive(Clone)]
+struct Recorder {
+    max:     Level,
+    records: Arc<Mutex<Vec<(Level, Fields)>>>,
+}
+
+impl Recorder {
+    fn new(max: Level) -> Self { Recorder { max, records: Arc::default() } }
+
+    fn records(&self) -> Vec<(Level, Fields)> { self.records.lock().unwrap().clone() }
+}
+
+struct Visitor(Fields);
+
+impl Visit for Visitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().into(), value.into()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.push((field.name().into(), format!("{value:?}")));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool { *metadata.level() <= self.max }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id { Id::from_u64(1) }
+
+    fn record(&self, _: &Id, values: &Record<'_>) {
+        let mut fields = Visitor(Vec::new());
+        values.record(&mut fields);
+        self.records.lock().unwrap().push((Level::INFO, fields.0));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Visitor(Vec::new());
+        event.record(&mut fields);
+        self.records.lock().unwrap().push((*event.metadata().level(), fields.0));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+static DISPLAYS: AtomicUsize = AtomicUsize::new(0);
+
+/// Error counting how many times it is displayed
+#[derive(Debug)]
+struct Counted(Option<Box<Counted>>);
+
+impl fmt::Display for Counted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DISPLAYS.fetch_add(1, Ordering::Relaxed);
+        f.write_str("counted")
+    }
+}
+
+impl StdError for Counted {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.as_deref().map(|x| x as &(dyn StdError + 'static))
+    }
+}
+
+#[derive(Debug)]
+struct Io(std::io::Error);
+
+impl fmt::Display for Io {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("request failed")
+    }
+}
+
+impl StdError for Io {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> { Some(&self.0) }
+}
+
+fn field<'a>(fields: &'a [(String, String)], name: &str) -> &'a str {
+    fields.iter().find(|(x, _)| x == name).map(|(_, x)| x.as_str()).unwrap()
+}
+
+#[test]
+fn events() {
+    let recorder = Recorder::new(Level::TRACE);
+    with_default(recorder.clone(), || {
+        FatalError::<Io>::Error(Io(std::io::Error::other("connection reset"))).trace();
+        let _ =
+            Err::<(), _>(FatalError::<Io>::Fatal(Io(std::io::Error::other("disk full"))))
+                .inspect_traced();
+    });
+    let records = recorder.records();
+    assert_eq!(records.len(), 2);
+    let (level, fields) = &records[0];
+    assert_eq!(*level, Level::WARN);
+    assert_eq!(field(fields, "message"), "non fatal error");
+    assert_eq!(field(fields, "severity"), "error");
+    assert_eq!(field(fields, "error"), "request failed");
+    assert_eq!(field(fields, "sources"), r#"["connection reset"]"#);
+    assert!(field(fields, "error_type").ends_with("Io"));
+    let (level, fields) = &records[1];
+    assert_eq!(*level, Level::ERROR);
+    assert_eq!(field(fields, "severity"), "fatal");
+    assert_eq!(field(fields, "sources"), r#"["disk full"]"#);
+}
+
+#[test]
+fn disabled_events_do_not_format_the_error() {
+    let recorder = Recorder::new(Level::ERROR);
+    let error = FatalError::<Counted>::Error(Counted(Some(Box::new(Counted(None)))));
+    with_default(recorder.clone(), || error.trace());
+    assert!(recorder.records().is_empty());
+    assert_eq!(DISPLAYS.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn span_status() {
+    let recorder = Recorder::new(Level::TRACE);
+    with_default(recorder.clone(), || {
+        let span = tracing::info_span!("job", status = tracing::field::Empty);
+        span.record_fatality(&Ok::<_, FatalError<Io>>(()));
+        span.record_fatality(&Err::<(), _>(FatalError::<Io>::Fatal(Io(
+            std::io::Error::other("disk full"),
+        ))));
+    });
+    let statuses: Vec<_> =
+        recorder.records().iter().map(|(_, x)| field(x, "status").to_string()).collect();
+    assert_eq!(statuses, ["ok", "fatal"]);
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "alloc")]
+use fatal_error::{FatalError, Severity, TrackedError};
+
+#[test]
+fn trail_records_transitions_in_order() {
+    let error = FatalError::Error("timeout").track();
+    let (error, escalated) = (error.escalate_because("too many retries"), line!());
+    let (error, deescalated) = (error.deescalate_because("fallback available"), line!());
+    let trail = error.trail().collect::<Vec<_>>();
+    assert_eq!(trail.len(), 2);
+    assert_eq!(trail[0].severity(), Severity::Fatal);
+    assert_eq!(trail[0].reason(), "too many retries");
+    assert_eq!(trail[0].location().file(), file!());
+    assert_eq!(trail[0].location().line(), escalated);
+    assert_eq!(trail[1].severity(), Severity::Error);
+    assert_eq!(trail[1].reason(), "fallback available");
+    assert_eq!(trail[1].location().file(), file!());
+    assert_eq!(trail[1].location().line(), deescalated);
+}
+
+#[test]
+fn noop_and_plain_transitions_record_nothing() {
+    let error: TrackedError<&str> = FatalError::Fatal("disk full").track();
+    let error = error.escalate_because("already fatal");
+    assert_eq!(error.trail().count(), 0);
+    let error = error.deescalate().escalate();
+    assert!(error.is_fatal());
+    assert_eq!(error.trail().count(), 0);
+}
+
+#[test]
+fn alternate_display_prints_the_trail() {
+    let (error, line) =
+        (FatalError::Error("timeout").track().escalate_because("retries"), line!());
+    assert_eq!(error.to_string(), "Fatal Error: timeout");
+    assert_eq!(
+        format!("{error:#}"),
+        format!(
+            "Fatal Error: timeout\n  escalated at {}:{line}:{}: retries",
+            file!(),
+            error.trail().next().unwrap().location().column()
+        )
+    );
+}
@@ -0,0 +1,10 @@
+use fatal_error::Fatality;
+
+#[derive(Fatality)]
+enum Error {
+    #[fatal]
+    #[recoverable]
+    Both,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use fatal_error::Fatality;
+
+#[derive(Fatality)]
+enum Error {
+    #[fatal]
+    Fatal,
+    Unclassified,
+}
+
+#[derive(Fatality)]
+struct Unclassified;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use fatal_error::Fatality;
+
+#[derive(Fatality)]
+enum Error {
+    #[fatality(transparent)]
+    Two(std::io::Error, std::io::Error),
+}
+
+fn main() {}
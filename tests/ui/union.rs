@@ -0,0 +1,9 @@
+use fatal_error::Fatality;
+
+#[derive(Fatality)]
+#[fatal]
+union Error {
+    code: u8,
+}
+
+fn main() {}
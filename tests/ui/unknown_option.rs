@@ -0,0 +1,9 @@
+use fatal_error::Fatality;
+
+#[derive(Fatality)]
+enum Error {
+    #[fatality(opaque)]
+    Unknown(std::io::Error),
+}
+
+fn main() {}